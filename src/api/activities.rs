@@ -2,23 +2,118 @@
 //! This module provides a set of functions to interact with the Strava API for activities.
 //! https://developers.strava.com/docs/reference/#api-Activities
 
-use crate::api::helpers::fetch_strava_data;
-use crate::api::strava_v3;
+use crate::api::helpers::{fetch_strava_data, fetch_strava_data_with_params};
+use crate::api::pagination::{Paginated, MAX_PER_PAGE};
 use crate::models::activities;
+use crate::models::athlete::SummaryAthlete;
+use crate::models::ids::ActivityId;
+use crate::util::timestamp::system_time_to_epoch;
 use log::{info, trace};
-use std::collections::HashMap;
+use std::time::SystemTime;
 
-/// Struct representing parameters for activities
-#[derive(Debug)]
+/// Parameters used to list the activities of the logged in athlete.
+///
+/// `before` and `after` are epoch timestamps in seconds, they can also be set from a `SystemTime`.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::api::activities::ActivitiesParams;
+///
+/// // Activities of January 2024, 100 per page
+/// let params = ActivitiesParams::new()
+///     .after(1704067200)
+///     .before(1706745600)
+///     .per_page(100);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct ActivitiesParams {
-    pub access_token: String,
-    pub before: Option<String>,
-    pub after: Option<String>,
-    pub per_page: Option<String>,
-    pub page: Option<String>,
+    before: Option<i64>,
+    after: Option<i64>,
+    per_page: Option<u32>,
+    page: Option<u32>,
+}
+
+impl ActivitiesParams {
+    /// Create parameters with nothing set, the API returns the first page of the latest activities
+    pub fn new() -> ActivitiesParams {
+        ActivitiesParams::default()
+    }
+
+    /// Only return activities that started before the epoch timestamp in seconds
+    pub fn before(mut self, epoch_seconds: i64) -> ActivitiesParams {
+        self.before = Some(epoch_seconds);
+        self
+    }
+
+    /// Only return activities that started after the epoch timestamp in seconds
+    pub fn after(mut self, epoch_seconds: i64) -> ActivitiesParams {
+        self.after = Some(epoch_seconds);
+        self
+    }
+
+    /// Only return activities that started before the given time
+    pub fn before_time(self, time: SystemTime) -> ActivitiesParams {
+        self.before(system_time_to_epoch(time))
+    }
+
+    /// Only return activities that started after the given time
+    pub fn after_time(self, time: SystemTime) -> ActivitiesParams {
+        self.after(system_time_to_epoch(time))
+    }
+
+    /// Page number to return, pages are numbered from 1
+    pub fn page(mut self, page: u32) -> ActivitiesParams {
+        self.page = Some(page);
+        self
+    }
+
+    /// Number of activities per page, at most 200
+    pub fn per_page(mut self, per_page: u32) -> ActivitiesParams {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Returns the before epoch timestamp in seconds if set
+    pub fn get_before(&self) -> Option<i64> {
+        self.before
+    }
+
+    /// Returns the after epoch timestamp in seconds if set
+    pub fn get_after(&self) -> Option<i64> {
+        self.after
+    }
+
+    /// Returns the page number if set
+    pub fn get_page(&self) -> Option<u32> {
+        self.page
+    }
+
+    /// Returns the page size if set
+    pub fn get_per_page(&self) -> Option<u32> {
+        self.per_page
+    }
+
+    /// Checks the paging values and the date range before calling the API
+    fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.page == Some(0) {
+            return Err("page must be 1 or greater".into());
+        }
+        if let Some(per_page) = self.per_page {
+            if per_page == 0 || per_page > MAX_PER_PAGE {
+                return Err(format!("per_page must be between 1 and {}", MAX_PER_PAGE).into());
+            }
+        }
+        if let (Some(before), Some(after)) = (self.before, self.after) {
+            if after >= before {
+                return Err("after must be earlier than before".into());
+            }
+        }
+        Ok(())
+    }
 }
 
-/// Takes an `ActivitiesParams` object and returns a `HashMap` of parameter names and their corresponding values.
+/// Takes an `ActivitiesParams` object and returns the query parameter names and their corresponding values.
 ///
 /// # Arguments
 ///
@@ -26,50 +121,156 @@ pub struct ActivitiesParams {
 ///
 /// # Returns
 ///
-/// A `HashMap<&str, String>` containing the parameter names and their corresponding values.
-fn prepare_params(params: &ActivitiesParams) -> HashMap<&str, String> {
-    let mut map_params = HashMap::new();
-    map_params.insert("access_token", params.access_token.clone());
-    if let Some(before) = &params.before {
-        map_params.insert("before", before.clone());
+/// A `Vec<(&str, String)>` containing the parameter names and their corresponding values.
+fn prepare_params(params: &ActivitiesParams) -> Vec<(&'static str, String)> {
+    let mut query = Vec::new();
+    if let Some(before) = params.before {
+        query.push(("before", before.to_string()));
     }
-    if let Some(after) = &params.after {
-        map_params.insert("after", after.clone());
+    if let Some(after) = params.after {
+        query.push(("after", after.to_string()));
     }
-    if let Some(per_page) = &params.per_page {
-        map_params.insert("per_page", per_page.clone());
+    if let Some(per_page) = params.per_page {
+        query.push(("per_page", per_page.to_string()));
     }
-    if let Some(page) = &params.page {
-        map_params.insert("page", page.clone());
+    if let Some(page) = params.page {
+        query.push(("page", page.to_string()));
     }
-    map_params
+    query
 }
 
 /// Get the activities from the Strava API for logged in athlete
 /// https://developers.strava.com/docs/reference/#api-Activities
-/// Arguments: access_token: &str
-/// Returns: JSON object from ActivityCollection
+/// Arguments: access_token: &str, params: &ActivitiesParams
+/// Returns: JSON object from ActivityCollection, or an error if the params are invalid
 pub fn get_activities(
+    access_token: &str,
     params: &ActivitiesParams,
 ) -> Result<activities::ActivityCollection, Box<dyn std::error::Error>> {
-    let map_params = prepare_params(params);
-    trace!("Athlete API parameters: {:?}", map_params);
-    // This is using a very specific call to the strava API passing in optional params
-    // the standard fetch_strava_data helper would not work here so calling the request manually
-    let client = reqwest::blocking::Client::new();
-    let url = strava_v3("athlete/activities".to_string());
-    let response = client.get(url).query(&map_params).send()?;
-    let json: activities::ActivityCollection = response.json()?;
-    Ok(json)
+    params.validate()?;
+    let query = prepare_params(params);
+    trace!("Athlete API parameters: {:?}", query);
+    fetch_strava_data_with_params("athlete/activities".to_string(), access_token, &query)
+}
+
+/// Get every activity of the logged in athlete matching the params, pages are fetched as the
+/// iterator is consumed starting from the page set in the params
+/// Arguments: access_token: &str, params: &ActivitiesParams
+/// Returns: iterator of SummaryActivity, or an error if the params are invalid
+pub fn get_activities_paginated(
+    access_token: &str,
+    params: &ActivitiesParams,
+) -> Result<Paginated<activities::SummaryActivity>, Box<dyn std::error::Error>> {
+    params.validate()?;
+    let mut paginated = Paginated::new("athlete/activities".to_string(), access_token);
+    if let Some(before) = params.before {
+        paginated = paginated.param("before", before);
+    }
+    if let Some(after) = params.after {
+        paginated = paginated.param("after", after);
+    }
+    if let Some(per_page) = params.per_page {
+        paginated = paginated.per_page(per_page);
+    }
+    if let Some(page) = params.page {
+        paginated = paginated.start_page(page);
+    }
+    Ok(paginated)
 }
 
 /// Get activity by ID
-/// Arguments: access_token: &str, activity_id: &str
-/// Returns json object from ActivityElement model
+/// Arguments: access_token: &str, activity_id: ActivityId
+/// Returns json object from DetailedActivity model
 pub fn get_activities_by_id(
     access_token: &str,
-    activity_id: &str,
-) -> Result<activities::ActivityElement, Box<dyn std::error::Error>> {
+    activity_id: ActivityId,
+) -> Result<activities::DetailedActivity, Box<dyn std::error::Error>> {
     info!("Calling Activities by ID API\n");
     fetch_strava_data(format!("/activities/{}", activity_id), access_token)
 }
+
+/// Get the laps of an activity
+/// https://developers.strava.com/docs/reference/#api-Activities-getLapsByActivityId
+/// Arguments: access_token: &str, activity_id: ActivityId
+/// Returns json object from Laps model
+pub fn get_activity_laps(
+    access_token: &str,
+    activity_id: ActivityId,
+) -> Result<activities::Laps, Box<dyn std::error::Error>> {
+    info!("Calling Activity Laps API\n");
+    fetch_strava_data(format!("activities/{}/laps", activity_id), access_token)
+}
+
+/// Get the heart rate and power zones of an activity, requires a Strava subscription
+/// https://developers.strava.com/docs/reference/#api-Activities-getZonesByActivityId
+/// Arguments: access_token: &str, activity_id: ActivityId
+/// Returns json object from ActivityZones model
+pub fn get_activity_zones(
+    access_token: &str,
+    activity_id: ActivityId,
+) -> Result<activities::ActivityZones, Box<dyn std::error::Error>> {
+    info!("Calling Activity Zones API\n");
+    fetch_strava_data(format!("activities/{}/zones", activity_id), access_token)
+}
+
+/// Get the comments of an activity, pages are fetched as the iterator is consumed
+/// https://developers.strava.com/docs/reference/#api-Activities-getCommentsByActivityId
+/// Arguments: access_token: &str, activity_id: ActivityId
+/// Returns an iterator of Comment models
+pub fn get_activity_comments(
+    access_token: &str,
+    activity_id: ActivityId,
+) -> Paginated<activities::Comment> {
+    info!("Calling Activity Comments API\n");
+    Paginated::new(format!("activities/{}/comments", activity_id), access_token)
+}
+
+/// Get the athletes who gave kudos to an activity, pages are fetched as the iterator is consumed
+/// https://developers.strava.com/docs/reference/#api-Activities-getKudoersByActivityId
+/// Arguments: access_token: &str, activity_id: ActivityId
+/// Returns an iterator of SummaryAthlete models
+pub fn get_activity_kudoers(
+    access_token: &str,
+    activity_id: ActivityId,
+) -> Paginated<SummaryAthlete> {
+    info!("Calling Activity Kudoers API\n");
+    Paginated::new(format!("activities/{}/kudos", activity_id), access_token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn prepare_params_uses_epoch_seconds_without_access_token() {
+        let params = ActivitiesParams::new()
+            .after_time(UNIX_EPOCH + Duration::from_secs(1704067200))
+            .before(1706745600)
+            .page(2)
+            .per_page(200);
+
+        assert!(params.validate().is_ok());
+        assert_eq!(
+            prepare_params(&params),
+            vec![
+                ("before", "1706745600".to_string()),
+                ("after", "1704067200".to_string()),
+                ("per_page", "200".to_string()),
+                ("page", "2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_invalid_paging_and_ranges() {
+        assert!(ActivitiesParams::new().per_page(201).validate().is_err());
+        assert!(ActivitiesParams::new().per_page(0).validate().is_err());
+        assert!(ActivitiesParams::new().page(0).validate().is_err());
+        assert!(ActivitiesParams::new()
+            .after(1706745600)
+            .before(1704067200)
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! # Module activity_query
+//! Client-side filtering of the logged in athlete's activities.
+//! Date bounds are sent to the API as `before`/`after`, every other filter is evaluated on each
+//! activity as the paginated results are consumed.
+
+use crate::api::activities::{get_activities_paginated, ActivitiesParams};
+use crate::models::activities::{SportType, SummaryActivity};
+use crate::models::ids::GearId;
+use crate::util::timestamp::system_time_to_epoch;
+use log::trace;
+use std::error::Error;
+use std::time::SystemTime;
+
+/// Query used to filter activities by type, date, distance, duration, flags and gear.
+///
+/// Filters that are not set match every activity.
+///
+/// # Examples
+///
+/// ```no_run
+/// use strava_client_rs::api::activity_query::ActivityQuery;
+/// use strava_client_rs::models::activities::SportType;
+///
+/// // Outdoor runs of January 2024 longer than 10km with heart rate
+/// let runs = ActivityQuery::new()
+///     .sport_type(SportType::Run)
+///     .after(1704067200)
+///     .before(1706745600)
+///     .min_distance(10_000.0)
+///     .trainer(false)
+///     .has_heartrate(true)
+///     .run("access_token")
+///     .unwrap();
+/// for run in runs {
+///     println!("{}", run.unwrap().name);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityQuery {
+    sport_types: Vec<SportType>,
+    before: Option<i64>,
+    after: Option<i64>,
+    min_distance: Option<f64>,
+    max_distance: Option<f64>,
+    min_moving_time: Option<i64>,
+    max_moving_time: Option<i64>,
+    commute: Option<bool>,
+    trainer: Option<bool>,
+    manual: Option<bool>,
+    private: Option<bool>,
+    gear_id: Option<GearId>,
+    has_heartrate: Option<bool>,
+    has_power: Option<bool>,
+    per_page: Option<u32>,
+}
+
+impl ActivityQuery {
+    /// Create a query matching every activity
+    pub fn new() -> ActivityQuery {
+        ActivityQuery::default()
+    }
+
+    /// Match activities of the sport type, can be called several times to match any of the types
+    pub fn sport_type(mut self, sport_type: SportType) -> ActivityQuery {
+        self.sport_types.push(sport_type);
+        self
+    }
+
+    /// Match activities that started before the epoch timestamp in seconds
+    pub fn before(mut self, epoch_seconds: i64) -> ActivityQuery {
+        self.before = Some(epoch_seconds);
+        self
+    }
+
+    /// Match activities that started after the epoch timestamp in seconds
+    pub fn after(mut self, epoch_seconds: i64) -> ActivityQuery {
+        self.after = Some(epoch_seconds);
+        self
+    }
+
+    /// Match activities that started before the given time
+    pub fn before_time(self, time: SystemTime) -> ActivityQuery {
+        self.before(system_time_to_epoch(time))
+    }
+
+    /// Match activities that started after the given time
+    pub fn after_time(self, time: SystemTime) -> ActivityQuery {
+        self.after(system_time_to_epoch(time))
+    }
+
+    /// Match activities at least this distance in meters
+    pub fn min_distance(mut self, meters: f64) -> ActivityQuery {
+        self.min_distance = Some(meters);
+        self
+    }
+
+    /// Match activities at most this distance in meters
+    pub fn max_distance(mut self, meters: f64) -> ActivityQuery {
+        self.max_distance = Some(meters);
+        self
+    }
+
+    /// Match activities with at least this moving time in seconds
+    pub fn min_moving_time(mut self, seconds: i64) -> ActivityQuery {
+        self.min_moving_time = Some(seconds);
+        self
+    }
+
+    /// Match activities with at most this moving time in seconds
+    pub fn max_moving_time(mut self, seconds: i64) -> ActivityQuery {
+        self.max_moving_time = Some(seconds);
+        self
+    }
+
+    /// Match activities flagged, or not flagged, as commutes
+    pub fn commute(mut self, commute: bool) -> ActivityQuery {
+        self.commute = Some(commute);
+        self
+    }
+
+    /// Match activities recorded, or not recorded, on a trainer
+    pub fn trainer(mut self, trainer: bool) -> ActivityQuery {
+        self.trainer = Some(trainer);
+        self
+    }
+
+    /// Match activities entered, or not entered, manually
+    pub fn manual(mut self, manual: bool) -> ActivityQuery {
+        self.manual = Some(manual);
+        self
+    }
+
+    /// Match private, or public, activities
+    pub fn private(mut self, private: bool) -> ActivityQuery {
+        self.private = Some(private);
+        self
+    }
+
+    /// Match activities recorded with the gear, e.g. `b1234567` for a bike
+    pub fn gear_id(mut self, gear_id: impl Into<GearId>) -> ActivityQuery {
+        self.gear_id = Some(gear_id.into());
+        self
+    }
+
+    /// Match activities with, or without, heart rate data
+    pub fn has_heartrate(mut self, has_heartrate: bool) -> ActivityQuery {
+        self.has_heartrate = Some(has_heartrate);
+        self
+    }
+
+    /// Match activities with, or without, power meter data
+    pub fn has_power(mut self, has_power: bool) -> ActivityQuery {
+        self.has_power = Some(has_power);
+        self
+    }
+
+    /// Number of activities fetched per page, at most 200
+    pub fn per_page(mut self, per_page: u32) -> ActivityQuery {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Returns true when the activity matches every filter of the query
+    pub fn matches(&self, activity: &SummaryActivity) -> bool {
+        let start = activity.start_date_epoch();
+        let in_range = |bound: Option<i64>, check: fn(i64, i64) -> bool| match (bound, start) {
+            (Some(bound), Some(start)) => check(start, bound),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        let flag = |filter: Option<bool>, value: bool| filter.is_none_or(|f| f == value);
+
+        (self.sport_types.is_empty() || self.sport_types.contains(&activity.sport_type))
+            && in_range(self.before, |start, before| start < before)
+            && in_range(self.after, |start, after| start > after)
+            && self
+                .min_distance
+                .is_none_or(|min| activity.distance.meters() >= min)
+            && self
+                .max_distance
+                .is_none_or(|max| activity.distance.meters() <= max)
+            && self
+                .min_moving_time
+                .is_none_or(|min| activity.moving_time.seconds() >= min as f64)
+            && self
+                .max_moving_time
+                .is_none_or(|max| activity.moving_time.seconds() <= max as f64)
+            && flag(self.commute, activity.commute)
+            && flag(self.trainer, activity.trainer)
+            && flag(self.manual, activity.manual)
+            && flag(self.private, activity.private)
+            && flag(self.has_heartrate, activity.has_heartrate)
+            && flag(self.has_power, activity.device_watts.unwrap_or(false))
+            && self
+                .gear_id
+                .as_ref()
+                .is_none_or(|gear_id| activity.gear_id.as_ref() == Some(gear_id))
+    }
+
+    /// Returns the activities of a collection matching the query
+    pub fn filter<'a>(&self, activities: &'a [SummaryActivity]) -> Vec<&'a SummaryActivity> {
+        activities
+            .iter()
+            .filter(|activity| self.matches(activity))
+            .collect()
+    }
+
+    /// Returns the listing parameters with the date bounds pushed down to the API
+    fn params(&self) -> ActivitiesParams {
+        let mut params = ActivitiesParams::new();
+        if let Some(before) = self.before {
+            params = params.before(before);
+        }
+        if let Some(after) = self.after {
+            params = params.after(after);
+        }
+        if let Some(per_page) = self.per_page {
+            params = params.per_page(per_page);
+        }
+        params
+    }
+
+    /// Run the query against the activities of the logged in athlete.
+    ///
+    /// Pages are fetched lazily as the iterator is consumed and errors are passed through.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The user's access token.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of matching activities, or an error if the query bounds are invalid.
+    pub fn run(
+        self,
+        access_token: &str,
+    ) -> Result<impl Iterator<Item = Result<SummaryActivity, Box<dyn Error>>>, Box<dyn Error>> {
+        let params = self.params();
+        trace!("Activity query parameters: {:?}", params);
+        let activities = get_activities_paginated(access_token, &params)?;
+        Ok(activities.filter(move |activity| match activity {
+            Ok(activity) => self.matches(activity),
+            Err(_) => true,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(
+        sport_type: &str,
+        distance: f64,
+        start_date: &str,
+        gear_id: &str,
+    ) -> SummaryActivity {
+        // The required fields and the ones read by `matches`, the others default when missing
+        serde_json::from_value(serde_json::json!({
+            "resource_state": 2,
+            "athlete": {"id": 123, "resource_state": 1},
+            "id": 1,
+            "sport_type": sport_type,
+            "distance": distance,
+            "moving_time": 1800,
+            "start_date": start_date,
+            "start_date_local": start_date,
+            "timezone": "(GMT+00:00) Europe/London",
+            "utc_offset": 0.0,
+            "gear_id": gear_id,
+            "has_heartrate": true
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn query_filters_collection_by_type_distance_date_and_gear() {
+        let activities = vec![
+            activity("Run", 10_500.0, "2024-01-10T07:00:00Z", "g1"),
+            activity("Run", 5_000.0, "2024-01-11T07:00:00Z", "g1"),
+            activity("Ride", 40_000.0, "2024-01-12T07:00:00Z", "b1"),
+            activity("Run", 12_000.0, "2024-02-10T07:00:00Z", "g1"),
+            activity("Run", 11_000.0, "2024-01-13T07:00:00Z", "g2"),
+        ];
+        let query = ActivityQuery::new()
+            .sport_type(SportType::Run)
+            .after(1704067200)
+            .before(1706745600)
+            .min_distance(10_000.0)
+            .gear_id("g1")
+            .has_heartrate(true)
+            .has_power(false);
+
+        let matched = query.filter(&activities);
+
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].start_date_epoch(), Some(1704870000));
+    }
+
+    #[test]
+    fn query_pushes_date_bounds_to_params() {
+        let params = ActivityQuery::new()
+            .after(1704067200)
+            .before(1706745600)
+            .commute(true)
+            .params();
+
+        assert_eq!(params.get_after(), Some(1704067200));
+        assert_eq!(params.get_before(), Some(1706745600));
+    }
+}
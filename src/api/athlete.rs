@@ -5,6 +5,7 @@
 //! Returns: JSON object from AthleteCollection
 
 use crate::api::helpers::{fetch_strava_data, put_to_strava_api, strava_v3};
+use crate::models::ids::AthleteId;
 use crate::models::{athlete, clubs};
 use log::info;
 use reqwest::blocking::Response;
@@ -26,17 +27,27 @@ pub fn get_athlete(
 
 /// Get the athlete stats from the Strava API for a specific athlete
 /// https://developers.strava.com/docs/reference/#api-models-AthleteStats
-/// Arguments: access_token: &str, athlete_id: &str
+/// Arguments: access_token: &str, athlete_id: AthleteId
 /// Returns: JSON object from AthleteStats
-/// Example: let stats = get_athlete_stats("access_token", "athlete_id");
+/// Example: let stats = get_athlete_stats("access_token", AthleteId(134815));
 pub fn get_athlete_stats(
     access_token: &str,
-    athlete_id: &str,
+    athlete_id: AthleteId,
 ) -> Result<athlete::AthleteStats, Box<dyn std::error::Error>> {
     info!("Calling Athlete Stats API\n");
     fetch_strava_data(format!("athletes/{}/stats", athlete_id), access_token)
 }
 
+/// Get the heart rate and power zones of the logged in athlete, requires the profile:read_all scope
+/// https://developers.strava.com/docs/reference/#api-Athletes-getLoggedInAthleteZones
+/// Arguments: access_token: &str
+/// Returns: JSON object from Zones
+/// Example: let zones = get_athlete_zones("access_token");
+pub fn get_athlete_zones(access_token: &str) -> Result<athlete::Zones, Box<dyn std::error::Error>> {
+    info!("Calling Athlete Zones API\n");
+    fetch_strava_data("athlete/zones".to_string(), access_token)
+}
+
 /// Get the athlete clubs from the Strava API for a specific athlete
 /// Arguments: access_token: &str
 /// Returns: JSON object from ClubCollection
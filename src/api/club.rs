@@ -2,11 +2,16 @@
 //!
 //! `club.rs` This module is the main interface with the Strava API for club operations. It provides
 //! functionality to retrieve data related to a specific club such as the club details,
-//! club members, club admins, and club activities.
+//! club members, club admins, and club activities, and to join or leave a club.
+//! Members, admins and activities are paginated, the `_paginated` functions fetch the pages
+//! lazily while the others collect every page.
 
-use crate::api::helpers::fetch_strava_data;
+use crate::api::helpers::{fetch_strava_data, post_to_strava_api, strava_v3};
+use crate::api::pagination::{Paginated, MAX_PER_PAGE};
 use crate::models::clubs;
+use crate::models::ids::ClubId;
 use log::{info, trace};
+use std::collections::HashMap;
 
 /// Get club details by ID.
 ///
@@ -17,17 +22,17 @@ use log::{info, trace};
 ///
 /// # Returns
 ///
-/// * `Result<clubs::Club>` - Club object or a Boxed Error
+/// * `Result<clubs::DetailedClub>` - Club object or a Boxed Error
 pub fn get_club_by_id(
     access_token: &str,
-    club_id: &str,
-) -> Result<clubs::Club, Box<dyn std::error::Error>> {
+    club_id: ClubId,
+) -> Result<clubs::DetailedClub, Box<dyn std::error::Error>> {
     trace!("Club ID: {:?}", club_id);
     info!("Calling Clubs by ID Strava API");
     fetch_strava_data(format!("/clubs/{}", club_id), access_token)
 }
 
-/// Get club members by club ID.
+/// Get every club member by club ID, fetching all the pages.
 ///
 /// # Arguments
 ///
@@ -39,13 +44,27 @@ pub fn get_club_by_id(
 /// * `Result<clubs::ClubMembers>` - A list of club members or a Boxed Error
 pub fn get_club_members(
     access_token: &str,
-    club_id: &str,
+    club_id: ClubId,
 ) -> Result<clubs::ClubMembers, Box<dyn std::error::Error>> {
+    get_club_members_paginated(access_token, club_id).collect()
+}
+
+/// Get club members by club ID, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `club_id` - The ID of the club.
+///
+/// # Returns
+///
+/// * `Paginated<clubs::Member>` - An iterator of club members
+pub fn get_club_members_paginated(access_token: &str, club_id: ClubId) -> Paginated<clubs::Member> {
     info!("Calling Strava Club Members API");
-    fetch_strava_data(format!("/clubs/{}/members", club_id), access_token)
+    Paginated::new(format!("clubs/{}/members", club_id), access_token).per_page(MAX_PER_PAGE)
 }
 
-/// Get club admins by club ID.
+/// Get every club admin by club ID, fetching all the pages.
 ///
 /// # Arguments
 ///
@@ -57,13 +76,27 @@ pub fn get_club_members(
 /// * `Result<clubs::ClubAdmins>` - A list of club admins or a Boxed Error
 pub fn get_club_admins(
     access_token: &str,
-    club_id: &str,
+    club_id: ClubId,
 ) -> Result<clubs::ClubAdmins, Box<dyn std::error::Error>> {
+    get_club_admins_paginated(access_token, club_id).collect()
+}
+
+/// Get club admins by club ID, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `club_id` - The ID of the club.
+///
+/// # Returns
+///
+/// * `Paginated<clubs::Admin>` - An iterator of club admins
+pub fn get_club_admins_paginated(access_token: &str, club_id: ClubId) -> Paginated<clubs::Admin> {
     info!("Calling Strava Club Admins API");
-    fetch_strava_data(format!("/clubs/{}/admins", club_id), access_token)
+    Paginated::new(format!("clubs/{}/admins", club_id), access_token).per_page(MAX_PER_PAGE)
 }
 
-/// Get club activities by club ID.
+/// Get every recent club activity by club ID, fetching all the pages.
 ///
 /// # Arguments
 ///
@@ -75,8 +108,104 @@ pub fn get_club_admins(
 /// * `Result<clubs::ClubActivities>` - A list of club activities or a Boxed Error
 pub fn get_club_activities(
     access_token: &str,
-    club_id: &str,
+    club_id: ClubId,
 ) -> Result<clubs::ClubActivities, Box<dyn std::error::Error>> {
+    get_club_activities_paginated(access_token, club_id).collect()
+}
+
+/// Get recent club activities by club ID, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `club_id` - The ID of the club.
+///
+/// # Returns
+///
+/// * `Paginated<clubs::ClubActivity>` - An iterator of club activities
+pub fn get_club_activities_paginated(
+    access_token: &str,
+    club_id: ClubId,
+) -> Paginated<clubs::ClubActivity> {
     info!("Calling Strava Get Club Activities API");
-    fetch_strava_data(format!("/clubs/{}/activities", club_id), access_token)
+    Paginated::new(format!("clubs/{}/activities", club_id), access_token).per_page(MAX_PER_PAGE)
+}
+
+/// Join a club as the logged in athlete, private clubs create a pending membership request.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `club_id` - The ID of the club.
+///
+/// # Returns
+///
+/// * `Result<clubs::ClubMembershipResponse>` - The membership state or a Boxed Error
+pub fn join_club(
+    access_token: &str,
+    club_id: ClubId,
+) -> Result<clubs::ClubMembershipResponse, Box<dyn std::error::Error>> {
+    trace!("Club ID: {:?}", club_id);
+    info!("Calling Strava Join Club API");
+    let url = strava_v3(format!("clubs/{}/join", club_id));
+    let membership = post_to_strava_api(url, access_token, HashMap::new())?.json()?;
+    Ok(membership)
+}
+
+/// Leave a club as the logged in athlete.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `club_id` - The ID of the club.
+///
+/// # Returns
+///
+/// * `Result<clubs::ClubMembershipResponse>` - The membership state or a Boxed Error
+pub fn leave_club(
+    access_token: &str,
+    club_id: ClubId,
+) -> Result<clubs::ClubMembershipResponse, Box<dyn std::error::Error>> {
+    trace!("Club ID: {:?}", club_id);
+    info!("Calling Strava Leave Club API");
+    let url = strava_v3(format!("clubs/{}/leave", club_id));
+    let membership = post_to_strava_api(url, access_token, HashMap::new())?.json()?;
+    Ok(membership)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginated_club_lists_request_full_pages() {
+        let members = get_club_members_paginated("token", ClubId(231407)).fetcher(
+            |path, access_token, params| {
+                assert_eq!(path, "clubs/231407/members");
+                assert_eq!(access_token, "token");
+                assert_eq!(
+                    params,
+                    [
+                        ("page", "1".to_string()),
+                        ("per_page", MAX_PER_PAGE.to_string())
+                    ]
+                );
+                Ok(Vec::new())
+            },
+        );
+        let admins = get_club_admins_paginated("token", ClubId(231407)).fetcher(|path, _, _| {
+            assert_eq!(path, "clubs/231407/admins");
+            Ok(Vec::new())
+        });
+        let activities =
+            get_club_activities_paginated("token", ClubId(231407)).fetcher(|path, _, params| {
+                assert_eq!(path, "clubs/231407/activities");
+                assert!(params.contains(&("per_page", MAX_PER_PAGE.to_string())));
+                Ok(Vec::new())
+            });
+
+        assert_eq!(members.count(), 0);
+        assert_eq!(admins.count(), 0);
+        assert_eq!(activities.count(), 0);
+    }
 }
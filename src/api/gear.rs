@@ -1,19 +1,41 @@
 //! # Gear Module
 //! This module is responsible for retrieving gear information from an API.
 //! It makes use of the gear id and access token to pull the info from Strava Gear API
+//! and combines it with the athlete's bikes and shoes into an inventory
 
+use crate::api::athlete::get_athlete;
 use crate::api::helpers::fetch_strava_data;
-use crate::models::gear::GearCollection;
+use crate::models::gear::{GearCollection, GearInventory, SummaryGear};
+use crate::models::ids::GearId;
 use log::{info, trace};
 
 /// Function get_gear
-/// Arguments: access_token: &str, gear_id: &str
+/// Arguments: access_token: &str, gear_id: GearId
 /// Returns gear object
 pub fn get_gear(
     access_token: &str,
-    gear_id: &str,
+    gear_id: GearId,
 ) -> Result<GearCollection, Box<dyn std::error::Error>> {
     trace!("Gear ID {}\n", gear_id);
     info!("Calling Strava Gear API");
     fetch_strava_data(format!("gear/{}", gear_id), access_token)
 }
+
+/// Function get_gear_inventory
+/// Gets the bikes and shoes listed on the logged in athlete, then the details of each item,
+/// retired gear included. Makes one call per item, requires the profile:read_all scope.
+/// Arguments: access_token: &str
+/// Returns GearInventory of detailed gear
+pub fn get_gear_inventory(access_token: &str) -> Result<GearInventory, Box<dyn std::error::Error>> {
+    let athlete = get_athlete(access_token)?;
+    info!("Calling Strava Gear API for the athlete inventory");
+    let details = |gear: &[SummaryGear]| {
+        gear.iter()
+            .map(|item| get_gear(access_token, item.id.clone()))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(GearInventory {
+        bikes: details(athlete.get_bikes())?,
+        shoes: details(athlete.get_shoes())?,
+    })
+}
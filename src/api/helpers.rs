@@ -2,6 +2,7 @@
 //! Helper Functions for the calling the Strava API
 
 use log::warn;
+use reqwest::blocking::multipart::Form;
 use reqwest::blocking::{RequestBuilder, Response};
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
@@ -25,7 +26,7 @@ pub fn strava_v3(path: String) -> String {
 /// # Returns
 ///
 /// * `Result<Response, Box<dyn std::error::Error>>` - Result containing the response on success,
-///    or an error on failure.
+///   or an error on failure.
 ///
 /// # Errors
 ///
@@ -35,10 +36,7 @@ fn send_request(request: RequestBuilder) -> Result<Response, Box<dyn std::error:
 
     if response.status().is_client_error() {
         warn!("API request returned an error: {:?}", response);
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            API_ERROR_MESSAGE,
-        )));
+        return Err(Box::new(std::io::Error::other(API_ERROR_MESSAGE)));
     }
 
     Ok(response)
@@ -91,6 +89,28 @@ pub fn put_to_strava_api(
     send_request(request)
 }
 
+/// Sends a POST request to the Strava API with the provided URL, access token, and parameters.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the Strava API endpoint as a String.
+/// * `access_token` - The access token for authentication as a &str.
+/// * `params` - A HashMap containing the parameters to be included in the request.
+///
+/// # Returns
+///
+/// * `Result<Response, Box<dyn std::error::Error>>` - The result of the request, either a `Response` if successful or an error if not.
+pub fn post_to_strava_api(
+    url: String,
+    access_token: &str,
+    params: HashMap<&str, &str>,
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let request = client.post(url).bearer_auth(access_token).form(&params);
+
+    send_request(request)
+}
+
 pub fn fetch_strava_data<T: DeserializeOwned>(
     api_path: String,
     access_token: &str,
@@ -100,3 +120,111 @@ pub fn fetch_strava_data<T: DeserializeOwned>(
     let data: T = response.json()?;
     Ok(data)
 }
+
+/// Sends a GET request with query parameters to the Strava API and deserializes the response.
+///
+/// # Arguments
+///
+/// * `api_path` - The path of the Strava API endpoint, relative to the v3 base URL.
+/// * `access_token` - The access token for authentication.
+/// * `params` - Query parameters to append to the request.
+///
+/// # Returns
+///
+/// * `Result<T, Box<dyn std::error::Error>>` - The deserialized response or an error.
+pub fn fetch_strava_data_with_params<T: DeserializeOwned>(
+    api_path: String,
+    access_token: &str,
+    params: &[(&str, String)],
+) -> Result<T, Box<dyn std::error::Error>> {
+    let url = strava_v3(api_path);
+    let client = reqwest::blocking::Client::new();
+    let request = client.get(url).bearer_auth(access_token).query(params);
+    let data: T = send_request(request)?.json()?;
+    Ok(data)
+}
+
+/// Sends a multipart POST request to the Strava API, used to upload files.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the Strava API endpoint.
+/// * `access_token` - The access token for authentication.
+/// * `form` - The multipart form holding the file and its fields.
+///
+/// # Returns
+///
+/// * `Result<Response, Box<dyn std::error::Error>>` - The result of the request, either a `Response` if successful or an error if not.
+pub fn post_multipart_to_strava_api(
+    url: String,
+    access_token: &str,
+    form: Form,
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let request = client.post(url).bearer_auth(access_token).multipart(form);
+
+    send_request(request)
+}
+
+/// Sends a form POST request to the Strava API without an athlete token.
+/// Used by the endpoints authenticated with the application client id and secret.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the Strava API endpoint.
+/// * `params` - The form fields, including the client credentials.
+///
+/// # Returns
+///
+/// * `Result<Response, Box<dyn std::error::Error>>` - The result of the request, either a `Response` if successful or an error if not.
+pub fn post_with_client_credentials(
+    url: String,
+    params: &[(&str, &str)],
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let request = client.post(url).form(params);
+
+    send_request(request)
+}
+
+/// Sends a GET request with query parameters to the Strava API without an athlete token.
+/// Used by the endpoints authenticated with the application client id and secret.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the Strava API endpoint.
+/// * `params` - The query parameters, including the client credentials.
+///
+/// # Returns
+///
+/// * `Result<Response, Box<dyn std::error::Error>>` - The result of the request, either a `Response` if successful or an error if not.
+pub fn fetch_with_client_credentials(
+    url: String,
+    params: &[(&str, &str)],
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let request = client.get(url).query(params);
+
+    send_request(request)
+}
+
+/// Sends a DELETE request with query parameters to the Strava API without an athlete token.
+/// Used by the endpoints authenticated with the application client id and secret.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the Strava API endpoint.
+/// * `params` - The query parameters, including the client credentials.
+///
+/// # Returns
+///
+/// * `Result<Response, Box<dyn std::error::Error>>` - The result of the request, either a `Response` if successful or an error if not.
+pub fn delete_with_client_credentials(
+    url: String,
+    params: &[(&str, &str)],
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let request = client.delete(url).query(params);
+
+    send_request(request)
+}
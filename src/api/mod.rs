@@ -10,7 +10,15 @@ pub use self::oauth::auth::get_refresh_token;
 pub mod oauth;
 
 pub mod activities;
+pub mod activity_query;
 pub mod athlete;
 pub mod club;
 pub mod gear;
 pub mod helpers;
+pub mod pagination;
+pub mod push_subscriptions;
+pub mod routes;
+pub mod segment_efforts;
+pub mod segments;
+pub mod streams;
+pub mod uploads;
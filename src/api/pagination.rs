@@ -0,0 +1,193 @@
+//! # pagination.rs
+//! Lazily walks the Strava endpoints that are paginated with `page` and `per_page`.
+//! Pages are only requested from the API when the previous one has been consumed.
+
+use crate::api::helpers::fetch_strava_data_with_params;
+use log::trace;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+
+/// Number of items Strava returns per page when `per_page` is not sent
+pub const DEFAULT_PER_PAGE: u32 = 30;
+/// Largest `per_page` value accepted by the Strava API
+pub const MAX_PER_PAGE: u32 = 200;
+
+/// Function fetching one page from the API path with the access token and query parameters
+type PageFetcher<T> = fn(String, &str, &[(&str, String)]) -> Result<Vec<T>, Box<dyn Error>>;
+
+/// Iterator over every item of a paginated Strava endpoint.
+///
+/// Each call to `next` yields one item, fetching the next page when the current one is exhausted.
+/// Iteration stops after a short page, or after the first error which is yielded once.
+///
+/// # Examples
+///
+/// ```no_run
+/// use strava_client_rs::api::pagination::Paginated;
+/// use strava_client_rs::models::activities::Comment;
+///
+/// let comments = Paginated::<Comment>::new("activities/1234/comments".to_string(), "access_token")
+///     .per_page(50);
+/// for comment in comments {
+///     println!("{}", comment.unwrap().text);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Paginated<T> {
+    api_path: String,
+    access_token: String,
+    params: Vec<(&'static str, String)>,
+    per_page: u32,
+    page: u32,
+    buffer: std::vec::IntoIter<T>,
+    finished: bool,
+    fetch: PageFetcher<T>,
+}
+
+impl<T: DeserializeOwned> Paginated<T> {
+    /// Create a paginator for the API path, starting at page 1 with the default page size
+    pub fn new(api_path: String, access_token: &str) -> Paginated<T> {
+        Paginated {
+            api_path,
+            access_token: access_token.to_string(),
+            params: Vec::new(),
+            per_page: DEFAULT_PER_PAGE,
+            page: 1,
+            buffer: Vec::new().into_iter(),
+            finished: false,
+            fetch: fetch_strava_data_with_params,
+        }
+    }
+
+    /// Fetch the pages with another function instead of calling the API
+    #[cfg(test)]
+    pub(crate) fn fetcher(mut self, fetch: PageFetcher<T>) -> Paginated<T> {
+        self.fetch = fetch;
+        self
+    }
+
+    /// Add an extra query parameter sent with every page request
+    pub fn param(mut self, key: &'static str, value: impl ToString) -> Paginated<T> {
+        self.params.push((key, value.to_string()));
+        self
+    }
+
+    /// Set the number of items per page, capped between 1 and `MAX_PER_PAGE`
+    pub fn per_page(mut self, per_page: u32) -> Paginated<T> {
+        self.per_page = per_page.clamp(1, MAX_PER_PAGE);
+        self
+    }
+
+    /// Set the first page to request, pages are numbered from 1
+    pub fn start_page(mut self, page: u32) -> Paginated<T> {
+        self.page = page.max(1);
+        self
+    }
+
+    /// Fetch the next whole page, returns `None` once the last page has been read
+    pub fn next_page(&mut self) -> Option<Result<Vec<T>, Box<dyn Error>>> {
+        if self.finished {
+            return None;
+        }
+
+        let mut params = self.params.clone();
+        params.push(("page", self.page.to_string()));
+        params.push(("per_page", self.per_page.to_string()));
+        trace!("Fetching page {} of {}", self.page, self.api_path);
+
+        let result = (self.fetch)(self.api_path.clone(), &self.access_token, &params);
+        match &result {
+            Ok(items) if (items.len() as u32) < self.per_page => self.finished = true,
+            Ok(_) => self.page += 1,
+            Err(_) => self.finished = true,
+        }
+        Some(result)
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Paginated<T> {
+    type Item = Result<T, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffer.next() {
+                return Some(Ok(item));
+            }
+            match self.next_page()? {
+                Ok(items) => self.buffer = items.into_iter(),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the page number requested
+    fn page(params: &[(&str, String)]) -> u32 {
+        params
+            .iter()
+            .find(|(key, _)| *key == "page")
+            .and_then(|(_, page)| page.parse().ok())
+            .unwrap()
+    }
+
+    /// Returns the items of a paginator that yielded no error
+    fn items(paginated: Paginated<u32>) -> Vec<u32> {
+        paginated.map(|item| item.unwrap()).collect()
+    }
+
+    #[test]
+    fn stops_after_a_short_page() {
+        let paginated = Paginated::new("items".to_string(), "token")
+            .per_page(2)
+            .fetcher(|_, _, params| match page(params) {
+                1 => Ok(vec![1, 2]),
+                2 => Ok(vec![3]),
+                page => panic!("Page {} requested after the short page", page),
+            });
+
+        assert_eq!(items(paginated), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn stops_after_an_empty_last_page() {
+        let paginated = Paginated::new("items".to_string(), "token")
+            .per_page(2)
+            .param("before", 1704067200)
+            .fetcher(|_, _, params| {
+                assert_eq!(params[0], ("before", "1704067200".to_string()));
+                assert!(params.contains(&("per_page", "2".to_string())));
+                match page(params) {
+                    1 => Ok(vec![1, 2]),
+                    2 => Ok(vec![3, 4]),
+                    3 => Ok(Vec::new()),
+                    page => panic!("Page {} requested after the empty page", page),
+                }
+            });
+
+        assert_eq!(items(paginated), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stops_after_the_first_error() {
+        let mut paginated = Paginated::new("items".to_string(), "token")
+            .per_page(2)
+            .fetcher(|_, _, params| match page(params) {
+                1 => Ok(vec![1, 2]),
+                2 => Err("rate limit exceeded".into()),
+                _ => Ok(vec![5, 6]),
+            });
+
+        assert_eq!(paginated.next().unwrap().unwrap(), 1);
+        assert_eq!(paginated.next().unwrap().unwrap(), 2);
+        assert_eq!(
+            paginated.next().unwrap().unwrap_err().to_string(),
+            "rate limit exceeded"
+        );
+        assert!(paginated.next().is_none());
+        assert!(paginated.next_page().is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! # Push Subscriptions Module
+//! This module manages the webhook push subscription of the application.
+//! Strava allows one subscription per application, it is authenticated with the client id and
+//! secret of `auth::Config` instead of an athlete access token.
+//! https://developers.strava.com/docs/webhooks/
+
+use crate::api::auth::Config;
+use crate::api::helpers::{
+    delete_with_client_credentials, fetch_with_client_credentials, post_with_client_credentials,
+    strava_v3,
+};
+use crate::models::ids::SubscriptionId;
+use crate::models::webhooks::Subscription;
+use log::{info, trace};
+
+/// Returns the client credentials sent with every push subscription request
+fn credentials(config: &Config) -> Vec<(&'static str, &str)> {
+    vec![
+        ("client_id", config.client_id.as_str()),
+        ("client_secret", config.client_secret.as_str()),
+    ]
+}
+
+/// Returns the parameters creating a push subscription
+fn create_params<'a>(
+    config: &'a Config,
+    callback_url: &'a str,
+    verify_token: &'a str,
+) -> Vec<(&'static str, &'a str)> {
+    let mut params = credentials(config);
+    params.push(("callback_url", callback_url));
+    params.push(("verify_token", verify_token));
+    params
+}
+
+/// Create the push subscription of the application.
+///
+/// Strava validates the callback before answering, the server at `callback_url` must echo the
+/// `hub.challenge` of the validation request when `hub.verify_token` matches `verify_token`.
+///
+/// # Arguments
+///
+/// * `config` - The application config holding the client id and secret.
+/// * `callback_url` - The address where Strava will send the events.
+/// * `verify_token` - The token Strava sends back in the validation request.
+///
+/// # Returns
+///
+/// * `Result<Subscription>` - The created subscription, only its id is set, or a Boxed Error
+pub fn create_push_subscription(
+    config: &Config,
+    callback_url: &str,
+    verify_token: &str,
+) -> Result<Subscription, Box<dyn std::error::Error>> {
+    let params = create_params(config, callback_url, verify_token);
+    trace!("Push subscription callback URL: {}", callback_url);
+    info!("Calling Strava Create Push Subscription API");
+    let url = strava_v3("push_subscriptions".to_string());
+    let subscription = post_with_client_credentials(url, &params)?.json()?;
+    Ok(subscription)
+}
+
+/// Get the push subscription of the application.
+///
+/// # Arguments
+///
+/// * `config` - The application config holding the client id and secret.
+///
+/// # Returns
+///
+/// * `Result<Option<Subscription>>` - The subscription, `None` when there is none, or a Boxed Error
+pub fn get_push_subscription(
+    config: &Config,
+) -> Result<Option<Subscription>, Box<dyn std::error::Error>> {
+    info!("Calling Strava View Push Subscription API");
+    let url = strava_v3("push_subscriptions".to_string());
+    let subscriptions: Vec<Subscription> =
+        fetch_with_client_credentials(url, &credentials(config))?.json()?;
+    Ok(subscriptions.into_iter().next())
+}
+
+/// Delete the push subscription of the application, Strava stops sending events immediately.
+///
+/// # Arguments
+///
+/// * `config` - The application config holding the client id and secret.
+/// * `subscription_id` - The ID of the subscription.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing or a Boxed Error
+pub fn delete_push_subscription(
+    config: &Config,
+    subscription_id: SubscriptionId,
+) -> Result<(), Box<dyn std::error::Error>> {
+    trace!("Push subscription ID: {:?}", subscription_id);
+    info!("Calling Strava Delete Push Subscription API");
+    let url = strava_v3(format!("push_subscriptions/{}", subscription_id));
+    delete_with_client_credentials(url, &credentials(config))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn params_hold_client_credentials() {
+        let config = Config::new(
+            "5".to_string(),
+            "7b2946535949ae70f015d696d8ac602830ece412".to_string(),
+            String::new(),
+            "https://www.strava.com/oauth/authorize".to_string(),
+            "https://www.strava.com/oauth/token".to_string(),
+        );
+
+        assert_eq!(
+            create_params(&config, "https://example.com/webhook", "STRAVA"),
+            vec![
+                ("client_id", "5"),
+                ("client_secret", "7b2946535949ae70f015d696d8ac602830ece412"),
+                ("callback_url", "https://example.com/webhook"),
+                ("verify_token", "STRAVA"),
+            ]
+        );
+        assert_eq!(credentials(&config).len(), 2);
+
+        let subscription: Subscription =
+            serde_json::from_str(r#"{"id":120475,"resource_state":2}"#).unwrap();
+        assert_eq!(subscription.id, SubscriptionId(120475));
+        assert_eq!(subscription.id.to_string(), "120475");
+    }
+}
@@ -0,0 +1,139 @@
+//! # Routes Module
+//! This module lists and retrieves routes, and downloads them as GPX or TCX files for GPS units.
+//! https://developers.strava.com/docs/reference/#api-Routes
+
+use crate::api::helpers::{fetch_from_strava_api, fetch_strava_data, strava_v3};
+use crate::api::pagination::Paginated;
+use crate::models::ids::{AthleteId, RouteId};
+use crate::models::routes;
+use log::{info, trace};
+use std::fs;
+use std::path::Path;
+
+/// File formats a route can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteExportFormat {
+    Gpx,
+    Tcx,
+}
+
+impl RouteExportFormat {
+    /// Returns the path segment of the export endpoint for the format
+    fn endpoint(&self) -> &'static str {
+        match self {
+            RouteExportFormat::Gpx => "export_gpx",
+            RouteExportFormat::Tcx => "export_tcx",
+        }
+    }
+
+    /// Returns the file extension of the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RouteExportFormat::Gpx => "gpx",
+            RouteExportFormat::Tcx => "tcx",
+        }
+    }
+}
+
+/// Returns the URL of the export endpoint of the route in the format
+fn export_url(route_id: RouteId, format: RouteExportFormat) -> String {
+    strava_v3(format!("routes/{}/{}", route_id, format.endpoint()))
+}
+
+/// Get the routes created by an athlete, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `athlete_id` - The ID of the athlete.
+///
+/// # Returns
+///
+/// * `Paginated<routes::Route>` - An iterator of routes
+pub fn get_athlete_routes(access_token: &str, athlete_id: AthleteId) -> Paginated<routes::Route> {
+    info!("Calling Strava Athlete Routes API");
+    Paginated::new(format!("athletes/{}/routes", athlete_id), access_token)
+}
+
+/// Get route details by ID.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `route_id` - The ID of the route.
+///
+/// # Returns
+///
+/// * `Result<routes::Route>` - Route object or a Boxed Error
+pub fn get_route(
+    access_token: &str,
+    route_id: RouteId,
+) -> Result<routes::Route, Box<dyn std::error::Error>> {
+    trace!("Route ID: {:?}", route_id);
+    info!("Calling Strava Route by ID API");
+    fetch_strava_data(format!("routes/{}", route_id), access_token)
+}
+
+/// Download a route as a GPX or TCX file.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `route_id` - The ID of the route.
+/// * `format` - The file format of the export.
+///
+/// # Returns
+///
+/// * `Result<Vec<u8>>` - The raw content of the file or a Boxed Error
+pub fn export_route(
+    access_token: &str,
+    route_id: RouteId,
+    format: RouteExportFormat,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    trace!("Route ID: {:?} format: {:?}", route_id, format);
+    info!("Calling Strava Export Route API");
+    let response = fetch_from_strava_api(export_url(route_id, format), access_token)?;
+    Ok(response.bytes()?.to_vec())
+}
+
+/// Download a route as a GPX or TCX file and write it to a path, replacing any existing file.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `route_id` - The ID of the route.
+/// * `format` - The file format of the export.
+/// * `path` - The path of the file to write.
+///
+/// # Returns
+///
+/// * `Result<()>` - Nothing or a Boxed Error if the download or the write failed
+pub fn export_route_to_file<P: AsRef<Path>>(
+    access_token: &str,
+    route_id: RouteId,
+    format: RouteExportFormat,
+    path: P,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = export_route(access_token, route_id, format)?;
+    trace!("Writing route export to {:?}", path.as_ref());
+    fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_url_uses_format_endpoint() {
+        assert_eq!(
+            export_url(RouteId(2774478853), RouteExportFormat::Gpx),
+            "https://www.strava.com/api/v3/routes/2774478853/export_gpx"
+        );
+        assert_eq!(
+            export_url(RouteId(2774478853), RouteExportFormat::Tcx),
+            "https://www.strava.com/api/v3/routes/2774478853/export_tcx"
+        );
+        assert_eq!(RouteExportFormat::Tcx.extension(), "tcx");
+    }
+}
@@ -0,0 +1,171 @@
+//! # Segment Efforts Module
+//! This module retrieves the logged in athlete's efforts on a segment, requires a Strava subscription.
+//! https://developers.strava.com/docs/reference/#api-SegmentEfforts
+
+use crate::api::helpers::fetch_strava_data;
+use crate::api::pagination::{Paginated, MAX_PER_PAGE};
+use crate::models::ids::{SegmentEffortId, SegmentId};
+use crate::models::segments;
+use crate::util::timestamp::format_local_timestamp;
+use log::{info, trace};
+
+/// Parameters used to list the efforts on a segment.
+///
+/// The start and end dates are the local wall clock time of the efforts, given as seconds since
+/// `1970-01-01T00:00:00` local time.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::api::segment_efforts::SegmentEffortsParams;
+/// use strava_client_rs::models::ids::SegmentId;
+///
+/// // Efforts on the segment during 2024
+/// let params = SegmentEffortsParams::new(SegmentId(229781))
+///     .start_date_local(1704067200)
+///     .end_date_local(1735689599)
+///     .per_page(100);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentEffortsParams {
+    segment_id: SegmentId,
+    start_date_local: Option<i64>,
+    end_date_local: Option<i64>,
+    per_page: Option<u32>,
+}
+
+impl SegmentEffortsParams {
+    /// Create parameters listing every effort on the segment
+    pub fn new(segment_id: SegmentId) -> SegmentEffortsParams {
+        SegmentEffortsParams {
+            segment_id,
+            start_date_local: None,
+            end_date_local: None,
+            per_page: None,
+        }
+    }
+
+    /// Only return efforts that started on or after the local time
+    pub fn start_date_local(mut self, local_seconds: i64) -> SegmentEffortsParams {
+        self.start_date_local = Some(local_seconds);
+        self
+    }
+
+    /// Only return efforts that started on or before the local time
+    pub fn end_date_local(mut self, local_seconds: i64) -> SegmentEffortsParams {
+        self.end_date_local = Some(local_seconds);
+        self
+    }
+
+    /// Number of efforts per page, at most 200
+    pub fn per_page(mut self, per_page: u32) -> SegmentEffortsParams {
+        self.per_page = Some(per_page);
+        self
+    }
+
+    /// Checks the page size and the date range before calling the API
+    fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(per_page) = self.per_page {
+            if per_page == 0 || per_page > MAX_PER_PAGE {
+                return Err(format!("per_page must be between 1 and {}", MAX_PER_PAGE).into());
+            }
+        }
+        if let (Some(start), Some(end)) = (self.start_date_local, self.end_date_local) {
+            if start > end {
+                return Err("start_date_local must not be after end_date_local".into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the query parameter names and their corresponding values, paging excluded
+    fn prepare_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("segment_id", self.segment_id.to_string())];
+        if let Some(start) = self.start_date_local {
+            params.push(("start_date_local", format_local_timestamp(start)));
+        }
+        if let Some(end) = self.end_date_local {
+            params.push(("end_date_local", format_local_timestamp(end)));
+        }
+        params
+    }
+}
+
+/// Get the logged in athlete's efforts on a segment, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `params` - The segment, date range and page size.
+///
+/// # Returns
+///
+/// * `Result<Paginated<segments::DetailedSegmentEffort>>` - An iterator of efforts or a Boxed
+///   Error if the params are invalid
+pub fn get_segment_efforts(
+    access_token: &str,
+    params: &SegmentEffortsParams,
+) -> Result<Paginated<segments::DetailedSegmentEffort>, Box<dyn std::error::Error>> {
+    params.validate()?;
+    let query = params.prepare_params();
+    trace!("Segment Efforts API parameters: {:?}", query);
+    info!("Calling Strava Segment Efforts API");
+
+    let mut paginated = Paginated::new("segment_efforts".to_string(), access_token);
+    for (key, value) in query {
+        paginated = paginated.param(key, value);
+    }
+    if let Some(per_page) = params.per_page {
+        paginated = paginated.per_page(per_page);
+    }
+    Ok(paginated)
+}
+
+/// Get a segment effort by ID.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `segment_effort_id` - The ID of the segment effort.
+///
+/// # Returns
+///
+/// * `Result<segments::DetailedSegmentEffort>` - Segment effort object or a Boxed Error
+pub fn get_segment_effort(
+    access_token: &str,
+    segment_effort_id: SegmentEffortId,
+) -> Result<segments::DetailedSegmentEffort, Box<dyn std::error::Error>> {
+    trace!("Segment Effort ID: {:?}", segment_effort_id);
+    info!("Calling Strava Segment Effort by ID API");
+    fetch_strava_data(
+        format!("segment_efforts/{}", segment_effort_id),
+        access_token,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepare_params_formats_local_dates() {
+        let params = SegmentEffortsParams::new(SegmentId(229781))
+            .start_date_local(1704067200)
+            .end_date_local(1735689599);
+
+        assert!(params.validate().is_ok());
+        assert_eq!(
+            params.prepare_params(),
+            vec![
+                ("segment_id", "229781".to_string()),
+                ("start_date_local", "2024-01-01T00:00:00".to_string()),
+                ("end_date_local", "2024-12-31T23:59:59".to_string()),
+            ]
+        );
+        assert!(SegmentEffortsParams::new(SegmentId(229781))
+            .start_date_local(1735689599)
+            .end_date_local(1704067200)
+            .validate()
+            .is_err());
+    }
+}
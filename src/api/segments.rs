@@ -0,0 +1,253 @@
+//! # Segments Module
+//! This module retrieves segments, explores the popular segments of an area and manages the
+//! segments starred by the logged in athlete.
+//! https://developers.strava.com/docs/reference/#api-Segments
+
+use crate::api::helpers::{
+    fetch_strava_data, fetch_strava_data_with_params, put_to_strava_api, strava_v3,
+};
+use crate::api::pagination::Paginated;
+use crate::models::geo::BoundingBox;
+use crate::models::ids::SegmentId;
+use crate::models::segments;
+use log::{info, trace};
+use std::collections::HashMap;
+
+/// Get segment details by ID.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `segment_id` - The ID of the segment.
+///
+/// # Returns
+///
+/// * `Result<segments::DetailedSegment>` - Segment object or a Boxed Error
+pub fn get_segment(
+    access_token: &str,
+    segment_id: SegmentId,
+) -> Result<segments::DetailedSegment, Box<dyn std::error::Error>> {
+    trace!("Segment ID: {:?}", segment_id);
+    info!("Calling Strava Segment by ID API");
+    fetch_strava_data(format!("segments/{}", segment_id), access_token)
+}
+
+/// Highest climb category, categories go from 0 (not categorized) to 5 (hors catégorie)
+const MAX_CLIMB_CATEGORY: u8 = 5;
+
+/// Activity type of the segments returned by the explorer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExploreActivityType {
+    Running,
+    Riding,
+}
+
+impl ExploreActivityType {
+    /// Returns the value used by the API for the activity type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExploreActivityType::Running => "running",
+            ExploreActivityType::Riding => "riding",
+        }
+    }
+}
+
+/// Parameters used to explore the segments of an area.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::api::segments::{ExploreActivityType, ExploreParams};
+/// use strava_client_rs::models::geo::{BoundingBox, LatLng};
+///
+/// // Categorized climbs around the Golden Gate bridge
+/// let bounds = BoundingBox::new(LatLng::new(37.78, -122.52), LatLng::new(37.86, -122.44)).unwrap();
+/// let params = ExploreParams::new(bounds)
+///     .activity_type(ExploreActivityType::Riding)
+///     .climb_category(1, 5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExploreParams {
+    bounds: BoundingBox,
+    activity_type: Option<ExploreActivityType>,
+    min_cat: Option<u8>,
+    max_cat: Option<u8>,
+}
+
+impl ExploreParams {
+    /// Create parameters exploring the area of the bounding box
+    pub fn new(bounds: BoundingBox) -> ExploreParams {
+        ExploreParams {
+            bounds,
+            activity_type: None,
+            min_cat: None,
+            max_cat: None,
+        }
+    }
+
+    /// Only return running or riding segments
+    pub fn activity_type(mut self, activity_type: ExploreActivityType) -> ExploreParams {
+        self.activity_type = Some(activity_type);
+        self
+    }
+
+    /// Only return segments with a climb category between min and max inclusive
+    pub fn climb_category(mut self, min: u8, max: u8) -> ExploreParams {
+        self.min_cat = Some(min);
+        self.max_cat = Some(max);
+        self
+    }
+
+    /// Checks the climb category range before calling the API
+    fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for category in [self.min_cat, self.max_cat].into_iter().flatten() {
+            if category > MAX_CLIMB_CATEGORY {
+                return Err(format!(
+                    "climb category must be between 0 and {}",
+                    MAX_CLIMB_CATEGORY
+                )
+                .into());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_cat, self.max_cat) {
+            if min > max {
+                return Err("min climb category must not exceed the max".into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the query parameter names and their corresponding values
+    fn prepare_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("bounds", self.bounds.to_string())];
+        if let Some(activity_type) = self.activity_type {
+            params.push(("activity_type", activity_type.as_str().to_string()));
+        }
+        if let Some(min_cat) = self.min_cat {
+            params.push(("min_cat", min_cat.to_string()));
+        }
+        if let Some(max_cat) = self.max_cat {
+            params.push(("max_cat", max_cat.to_string()));
+        }
+        params
+    }
+}
+
+/// Explore the top segments of an area, the API returns at most 10 segments.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `params` - The area, activity type and climb categories to explore.
+///
+/// # Returns
+///
+/// * `Result<Vec<segments::ExplorerSegment>>` - The segments of the area or a Boxed Error
+pub fn explore_segments(
+    access_token: &str,
+    params: &ExploreParams,
+) -> Result<Vec<segments::ExplorerSegment>, Box<dyn std::error::Error>> {
+    params.validate()?;
+    let query = params.prepare_params();
+    trace!("Explore Segments API parameters: {:?}", query);
+    info!("Calling Strava Explore Segments API");
+    let response: segments::ExplorerResponse =
+        fetch_strava_data_with_params("segments/explore".to_string(), access_token, &query)?;
+    Ok(response.segments)
+}
+
+/// Get the segments starred by the logged in athlete, pages are fetched as the iterator is consumed.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+///
+/// # Returns
+///
+/// * `Paginated<segments::SummarySegment>` - An iterator of starred segments
+pub fn get_starred_segments(access_token: &str) -> Paginated<segments::SummarySegment> {
+    info!("Calling Strava Starred Segments API");
+    Paginated::new("segments/starred".to_string(), access_token)
+}
+
+/// Returns the form starring or unstarring a segment
+fn star_form(starred: bool) -> HashMap<&'static str, &'static str> {
+    HashMap::from([("starred", if starred { "true" } else { "false" })])
+}
+
+/// Star or unstar a segment for the logged in athlete, requires the profile:write scope.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `segment_id` - The ID of the segment.
+/// * `starred` - True to star the segment, false to unstar it.
+///
+/// # Returns
+///
+/// * `Result<segments::DetailedSegment>` - The updated segment or a Boxed Error
+pub fn star_segment(
+    access_token: &str,
+    segment_id: SegmentId,
+    starred: bool,
+) -> Result<segments::DetailedSegment, Box<dyn std::error::Error>> {
+    let url = strava_v3(format!("segments/{}/starred", segment_id));
+    trace!("Segment ID: {:?} starred: {}", segment_id, starred);
+    info!("Calling Strava Star Segment API");
+    let segment = put_to_strava_api(url, access_token, star_form(starred))?.json()?;
+    Ok(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::geo::LatLng;
+
+    fn bounds() -> BoundingBox {
+        BoundingBox::new(LatLng::new(37.78, -122.52), LatLng::new(37.86, -122.44)).unwrap()
+    }
+
+    #[test]
+    fn explore_params_send_bounds_type_and_categories() {
+        let params = ExploreParams::new(bounds())
+            .activity_type(ExploreActivityType::Riding)
+            .climb_category(1, 5);
+
+        assert!(params.validate().is_ok());
+        assert_eq!(
+            params.prepare_params(),
+            vec![
+                ("bounds", "37.78,-122.52,37.86,-122.44".to_string()),
+                ("activity_type", "riding".to_string()),
+                ("min_cat", "1".to_string()),
+                ("max_cat", "5".to_string()),
+            ]
+        );
+        assert_eq!(
+            ExploreParams::new(bounds()).prepare_params(),
+            vec![("bounds", "37.78,-122.52,37.86,-122.44".to_string())]
+        );
+    }
+
+    #[test]
+    fn explore_params_reject_invalid_climb_categories() {
+        assert!(ExploreParams::new(bounds())
+            .climb_category(0, 0)
+            .validate()
+            .is_ok());
+        assert!(ExploreParams::new(bounds())
+            .climb_category(3, 2)
+            .validate()
+            .is_err());
+        assert!(ExploreParams::new(bounds())
+            .climb_category(0, 6)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn star_form_sends_the_starred_flag() {
+        assert_eq!(star_form(true), HashMap::from([("starred", "true")]));
+        assert_eq!(star_form(false), HashMap::from([("starred", "false")]));
+    }
+}
@@ -0,0 +1,219 @@
+//! # Streams Module
+//! This module retrieves the raw recorded data of activities, routes, segments and segment efforts
+//! as a set of aligned streams.
+//! https://developers.strava.com/docs/reference/#api-Streams
+
+use crate::api::helpers::{fetch_strava_data, fetch_strava_data_with_params};
+use crate::models::ids::{ActivityId, RouteId, SegmentEffortId, SegmentId};
+use crate::models::streams::{Resolution, StreamSet, StreamType, TypedStream};
+use log::{info, trace};
+
+/// Builds the query parameters shared by every streams endpoint
+///
+/// # Arguments
+///
+/// * `keys` - The stream types to request, every type is requested when empty.
+/// * `resolution` - The optional sampling resolution, the API returns all samples when `None`.
+///
+/// # Returns
+///
+/// A `Vec` of query parameter names and values, always keyed by type.
+fn prepare_params(
+    keys: &[StreamType],
+    resolution: Option<Resolution>,
+) -> Vec<(&'static str, String)> {
+    let keys = if keys.is_empty() {
+        &StreamType::ALL[..]
+    } else {
+        keys
+    };
+    let keys: Vec<&str> = keys.iter().map(|key| key.as_str()).collect();
+
+    let mut params = vec![
+        ("keys", keys.join(",")),
+        ("key_by_type", "true".to_string()),
+    ];
+    if let Some(resolution) = resolution {
+        params.push(("resolution", resolution.as_str().to_string()));
+    }
+    params
+}
+
+/// Builds the query parameters of the segment streams endpoint, requesting the three segment
+/// streams when `keys` is empty
+fn prepare_segment_params(
+    keys: &[StreamType],
+    resolution: Option<Resolution>,
+) -> Vec<(&'static str, String)> {
+    if keys.is_empty() {
+        prepare_params(&StreamType::SEGMENT, resolution)
+    } else {
+        prepare_params(keys, resolution)
+    }
+}
+
+/// Get the streams of an activity.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `activity_id` - The ID of the activity.
+/// * `keys` - The stream types to request, every type is requested when empty.
+/// * `resolution` - The optional sampling resolution.
+///
+/// # Returns
+///
+/// * `Result<StreamSet>` - The streams of the activity or a Boxed Error
+pub fn get_activity_streams(
+    access_token: &str,
+    activity_id: ActivityId,
+    keys: &[StreamType],
+    resolution: Option<Resolution>,
+) -> Result<StreamSet, Box<dyn std::error::Error>> {
+    let params = prepare_params(keys, resolution);
+    trace!("Activity Streams API parameters: {:?}", params);
+    info!("Calling Strava Activity Streams API");
+    fetch_strava_data_with_params(
+        format!("activities/{}/streams", activity_id),
+        access_token,
+        &params,
+    )
+}
+
+/// Get the streams of a route, the API always returns the latlng, distance and altitude streams.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `route_id` - The ID of the route.
+///
+/// # Returns
+///
+/// * `Result<StreamSet>` - The streams of the route or a Boxed Error
+pub fn get_route_streams(
+    access_token: &str,
+    route_id: RouteId,
+) -> Result<StreamSet, Box<dyn std::error::Error>> {
+    info!("Calling Strava Route Streams API");
+    // The routes endpoint ignores key_by_type and returns the streams as a list
+    let streams: Vec<TypedStream> =
+        fetch_strava_data(format!("routes/{}/streams", route_id), access_token)?;
+    Ok(StreamSet::from_list(streams)?)
+}
+
+/// Get the streams of a segment. Unlike activities, segments only have the `distance`, `latlng`
+/// and `altitude` streams listed in `StreamType::SEGMENT`, other types are not returned.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `segment_id` - The ID of the segment.
+/// * `keys` - The stream types to request, the three segment streams are requested when empty.
+/// * `resolution` - The optional sampling resolution.
+///
+/// # Returns
+///
+/// * `Result<StreamSet>` - The streams of the segment or a Boxed Error
+pub fn get_segment_streams(
+    access_token: &str,
+    segment_id: SegmentId,
+    keys: &[StreamType],
+    resolution: Option<Resolution>,
+) -> Result<StreamSet, Box<dyn std::error::Error>> {
+    let params = prepare_segment_params(keys, resolution);
+    trace!("Segment Streams API parameters: {:?}", params);
+    info!("Calling Strava Segment Streams API");
+    fetch_strava_data_with_params(
+        format!("segments/{}/streams", segment_id),
+        access_token,
+        &params,
+    )
+}
+
+/// Get the streams of a segment effort, the samples covering the segment within its activity.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `segment_effort_id` - The ID of the segment effort.
+/// * `keys` - The stream types to request, every type is requested when empty.
+/// * `resolution` - The optional sampling resolution.
+///
+/// # Returns
+///
+/// * `Result<StreamSet>` - The streams of the segment effort or a Boxed Error
+pub fn get_segment_effort_streams(
+    access_token: &str,
+    segment_effort_id: SegmentEffortId,
+    keys: &[StreamType],
+    resolution: Option<Resolution>,
+) -> Result<StreamSet, Box<dyn std::error::Error>> {
+    let params = prepare_params(keys, resolution);
+    trace!("Segment Effort Streams API parameters: {:?}", params);
+    info!("Calling Strava Segment Effort Streams API");
+    fetch_strava_data_with_params(
+        format!("segment_efforts/{}/streams", segment_effort_id),
+        access_token,
+        &params,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prepare_params_requests_every_type_by_default() {
+        let all: Vec<&str> = StreamType::ALL.iter().map(|key| key.as_str()).collect();
+
+        assert_eq!(
+            prepare_params(&[], None),
+            vec![("keys", all.join(",")), ("key_by_type", "true".to_string()),]
+        );
+    }
+
+    #[test]
+    fn prepare_params_sends_keys_and_resolution() {
+        assert_eq!(
+            prepare_params(
+                &[StreamType::Time, StreamType::Heartrate],
+                Some(Resolution::Low)
+            ),
+            vec![
+                ("keys", "time,heartrate".to_string()),
+                ("key_by_type", "true".to_string()),
+                ("resolution", "low".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_params_request_the_segment_streams_by_default() {
+        assert_eq!(
+            prepare_segment_params(&[], None)[0],
+            ("keys", "distance,latlng,altitude".to_string())
+        );
+        assert_eq!(
+            prepare_segment_params(&[StreamType::Altitude], None)[0],
+            ("keys", "altitude".to_string())
+        );
+    }
+
+    #[test]
+    fn route_streams_list_builds_a_stream_set() {
+        let sample = r#"[
+            {"type":"latlng","data":[[37.8,-122.4],[37.81,-122.41]],"series_type":"distance","original_size":2,"resolution":"high"},
+            {"type":"distance","data":[0.0,1410.2],"series_type":"distance","original_size":2,"resolution":"high"},
+            {"type":"altitude","data":[12.0,14.5],"series_type":"distance","original_size":2,"resolution":"high"}
+        ]"#;
+        let list: Vec<TypedStream> = serde_json::from_str(sample).unwrap();
+        let streams = StreamSet::from_list(list).unwrap();
+
+        assert_eq!(streams.len(), 2);
+        let distance = streams.distance.unwrap();
+        assert_eq!(distance.data, vec![0.0, 1410.2]);
+        assert_eq!(distance.series_type.as_deref(), Some("distance"));
+        assert_eq!(distance.original_size, Some(2));
+        assert!(streams.latlng.is_some() && streams.altitude.is_some());
+    }
+}
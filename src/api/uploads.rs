@@ -0,0 +1,265 @@
+//! # Uploads Module
+//! This module uploads activity files to Strava and polls the upload until the activity is created.
+//! https://developers.strava.com/docs/uploads/
+
+use crate::api::helpers::{fetch_strava_data, post_multipart_to_strava_api, strava_v3};
+use crate::models::ids::UploadId;
+use crate::models::uploads::Upload;
+use log::{info, trace};
+use reqwest::blocking::multipart::Form;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// File formats accepted by the uploads endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    Fit,
+    FitGz,
+    Tcx,
+    TcxGz,
+    Gpx,
+    GpxGz,
+}
+
+impl DataType {
+    /// Returns the value used by the API for the data type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataType::Fit => "fit",
+            DataType::FitGz => "fit.gz",
+            DataType::Tcx => "tcx",
+            DataType::TcxGz => "tcx.gz",
+            DataType::Gpx => "gpx",
+            DataType::GpxGz => "gpx.gz",
+        }
+    }
+
+    /// Detects the data type from the file extension, e.g. `ride.fit.gz`, ignoring case
+    pub fn from_path(path: &Path) -> Option<DataType> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        [
+            DataType::FitGz,
+            DataType::TcxGz,
+            DataType::GpxGz,
+            DataType::Fit,
+            DataType::Tcx,
+            DataType::Gpx,
+        ]
+        .into_iter()
+        .find(|data_type| name.ends_with(&format!(".{}", data_type.as_str())))
+    }
+}
+
+/// Parameters used to upload an activity file.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::api::uploads::UploadParams;
+///
+/// let params = UploadParams::new("indoor_ride.fit")
+///     .name("Trainer intervals")
+///     .trainer(true)
+///     .external_id("trainer-2024-01-10");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadParams {
+    file: PathBuf,
+    data_type: Option<DataType>,
+    name: Option<String>,
+    description: Option<String>,
+    trainer: Option<bool>,
+    commute: Option<bool>,
+    external_id: Option<String>,
+}
+
+impl UploadParams {
+    /// Create parameters uploading the file, the data type is detected from its extension
+    pub fn new<P: AsRef<Path>>(file: P) -> UploadParams {
+        UploadParams {
+            file: file.as_ref().to_path_buf(),
+            data_type: None,
+            name: None,
+            description: None,
+            trainer: None,
+            commute: None,
+            external_id: None,
+        }
+    }
+
+    /// Set the data type when the file extension doesn't match the format
+    pub fn data_type(mut self, data_type: DataType) -> UploadParams {
+        self.data_type = Some(data_type);
+        self
+    }
+
+    /// Name of the created activity
+    pub fn name(mut self, name: &str) -> UploadParams {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Description of the created activity
+    pub fn description(mut self, description: &str) -> UploadParams {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Flag the created activity as recorded on a trainer
+    pub fn trainer(mut self, trainer: bool) -> UploadParams {
+        self.trainer = Some(trainer);
+        self
+    }
+
+    /// Flag the created activity as a commute
+    pub fn commute(mut self, commute: bool) -> UploadParams {
+        self.commute = Some(commute);
+        self
+    }
+
+    /// Identifier of the file in the uploading system, returned with the upload
+    pub fn external_id(mut self, external_id: &str) -> UploadParams {
+        self.external_id = Some(external_id.to_string());
+        self
+    }
+
+    /// Returns the data type set or detected from the file extension
+    fn get_data_type(&self) -> Result<DataType, Box<dyn std::error::Error>> {
+        self.data_type
+            .or_else(|| DataType::from_path(&self.file))
+            .ok_or_else(|| {
+                format!(
+                    "Could not detect the data type of {:?}, expected a fit, tcx or gpx file, optionally gzipped",
+                    self.file
+                )
+                .into()
+            })
+    }
+
+    /// Builds the multipart form holding the file and the activity fields
+    fn prepare_form(&self) -> Result<Form, Box<dyn std::error::Error>> {
+        let mut form = Form::new()
+            .text("data_type", self.get_data_type()?.as_str())
+            .file("file", &self.file)?;
+        if let Some(name) = &self.name {
+            form = form.text("name", name.clone());
+        }
+        if let Some(description) = &self.description {
+            form = form.text("description", description.clone());
+        }
+        if let Some(trainer) = self.trainer {
+            form = form.text("trainer", if trainer { "1" } else { "0" });
+        }
+        if let Some(commute) = self.commute {
+            form = form.text("commute", if commute { "1" } else { "0" });
+        }
+        if let Some(external_id) = &self.external_id {
+            form = form.text("external_id", external_id.clone());
+        }
+        Ok(form)
+    }
+}
+
+/// Upload an activity file, requires the activity:write scope.
+///
+/// The activity is created asynchronously, use `wait_for_upload` to get its id.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `params` - The file and the fields of the activity.
+///
+/// # Returns
+///
+/// * `Result<Upload>` - The pending upload or a Boxed Error
+pub fn upload_activity(
+    access_token: &str,
+    params: &UploadParams,
+) -> Result<Upload, Box<dyn std::error::Error>> {
+    let form = params.prepare_form()?;
+    trace!("Uploading activity file {:?}", params.file);
+    info!("Calling Strava Upload Activity API");
+    let upload =
+        post_multipart_to_strava_api(strava_v3("uploads".to_string()), access_token, form)?
+            .json()?;
+    Ok(upload)
+}
+
+/// Get the processing state of an upload.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `upload_id` - The ID of the upload.
+///
+/// # Returns
+///
+/// * `Result<Upload>` - Upload object or a Boxed Error
+pub fn get_upload(
+    access_token: &str,
+    upload_id: UploadId,
+) -> Result<Upload, Box<dyn std::error::Error>> {
+    trace!("Upload ID: {:?}", upload_id);
+    info!("Calling Strava Get Upload API");
+    fetch_strava_data(format!("uploads/{}", upload_id), access_token)
+}
+
+/// Poll an upload until Strava assigned the activity id or reported an error.
+///
+/// Blocks the current thread. Strava recommends polling no more than once per second and
+/// usually processes a file within 8 seconds.
+///
+/// # Arguments
+///
+/// * `access_token` - The user's access token.
+/// * `upload_id` - The ID of the upload.
+/// * `poll_interval` - The time to wait between two polls.
+/// * `timeout` - The time after which polling gives up.
+///
+/// # Returns
+///
+/// * `Result<Upload>` - The processed upload, check `is_ready`, `is_error` and `is_duplicate`,
+///   or a Boxed Error if a poll failed or the upload was still processing after the timeout
+pub fn wait_for_upload(
+    access_token: &str,
+    upload_id: UploadId,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<Upload, Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    loop {
+        let upload = get_upload(access_token, upload_id)?;
+        trace!("Upload status: {}", upload.status);
+        if !upload.is_processing() {
+            return Ok(upload);
+        }
+        if started.elapsed() + poll_interval > timeout {
+            return Err(format!(
+                "Upload {} was still processing after {:?}",
+                upload_id, timeout
+            )
+            .into());
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_type_detected_from_extension() {
+        assert_eq!(
+            DataType::from_path(Path::new("ride.fit")),
+            Some(DataType::Fit)
+        );
+        assert_eq!(
+            DataType::from_path(Path::new("exports/Run.TCX.gz")),
+            Some(DataType::TcxGz)
+        );
+        assert_eq!(DataType::from_path(Path::new("route.kml")), None);
+        assert!(UploadParams::new("route.kml").get_data_type().is_err());
+    }
+}
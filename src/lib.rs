@@ -10,9 +10,17 @@
 //! * Athlete
 //! * Activities
 //! * Clubs
-//! * Gear
+//! * Gear, including an inventory of the athlete's bikes and shoes
+//! * Routes, including GPX and TCX export
+//! * Segments
+//! * Streams
 //!
-//! It supports write operations on athlete weight
+//! It supports write operations on athlete weight, starred segments and club membership, and activity file uploads
+//! It also manages the webhook push subscription of the application
+//!
+//! # Features
+//! * `chrono` - accessors parsing the model timestamps and timezones into chrono types, the fields stay strings
+//! * `test-util` - builders and a fake data generator of the models, for testing code that uses this crate
 //!
 //! # Examples
 //!
@@ -65,4 +73,6 @@
 
 pub mod api;
 pub mod models;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod util;
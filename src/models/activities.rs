@@ -2,177 +2,616 @@
 //! This model is used to represent the athlete object returned by the Strava API
 //! Documentation: https://developers.strava.com/docs/reference/#api-Activities
 //! Contains helper methods to convert units
+use crate::models::athlete::{MetaAthlete, SummaryAthlete};
+use crate::models::datetime::epoch_seconds;
+use crate::models::gear::SummaryGear;
+use crate::models::geo::LatLng;
+use crate::models::ids::{ActivityId, GearId, UploadId};
+use crate::models::segments::DetailedSegmentEffort;
+use crate::models::units::{Distance, Duration, Elevation, Pace, Speed};
+use crate::util::polyline;
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 
 /// Collection used to retrieve all activities
-pub type ActivityCollection = Vec<ActivityElement>;
+pub type ActivityCollection = Vec<SummaryActivity>;
 
-/// Single Activity Strut
+/// Summary activity, kept under its former name
+pub type ActivityElement = SummaryActivity;
+
+/// Summary representation of an activity, returned when listing activities.
+/// Counts, flags and measures missing or null for other athletes' activities default to zero
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-SummaryActivity
 #[derive(Serialize, Deserialize, Debug)]
-pub struct ActivityElement {
+pub struct SummaryActivity {
     pub resource_state: i64,
-    pub athlete: Athlete,
+    pub athlete: MetaAthlete,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub name: String,
-    pub distance: f64,
-    pub moving_time: i64,
-    pub elapsed_time: i64,
-    pub total_elevation_gain: f64,
-    pub sport_type: ActivityType,
-    pub workout_type: Option<serde_json::Value>,
-    pub id: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub distance: Distance,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub moving_time: Duration,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub elapsed_time: Duration,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub total_elevation_gain: Elevation,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub activity_type: Option<ActivityType>,
+    pub sport_type: SportType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workout_type: Option<WorkoutType>,
+    pub id: ActivityId,
     pub start_date: String,
     pub start_date_local: String,
     pub timezone: String,
     pub utc_offset: f64,
-    pub location_city: Option<serde_json::Value>,
-    pub location_state: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location_country: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub achievement_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub kudos_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub comment_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub athlete_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub photo_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub map: Map,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub trainer: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub commute: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub manual: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub private: bool,
-    pub visibility: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub flagged: bool,
-    pub gear_id: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gear_id: Option<GearId>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub start_latlng: Vec<f64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub end_latlng: Vec<f64>,
-    pub average_speed: f64,
-    pub max_speed: f64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub average_speed: Speed,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub max_speed: Speed,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub average_cadence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub average_temp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub average_watts: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_watts: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub weighted_average_watts: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub kilojoules: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub device_watts: Option<bool>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub has_heartrate: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub average_heartrate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_heartrate: Option<f64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub heartrate_opt_out: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub display_hide_heartrate_option: bool,
-    pub elev_high: Option<f64>,
-    pub elev_low: Option<f64>,
-    pub upload_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elev_high: Option<Elevation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elev_low: Option<Elevation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_id: Option<UploadId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub upload_id_str: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub external_id: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub from_accepted_tag: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub pr_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub total_photo_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub has_kudoed: bool,
 }
 
-impl ActivityElement {
-    /// Convert distance from km to miles
-    pub fn distance_in_miles(&self) -> i64 {
-        (self.distance * 0.000621371) as i64
+impl SummaryActivity {
+    /// Returns true when only the athlete can see the activity
+    pub fn is_only_me(&self) -> bool {
+        self.visibility == Some(Visibility::OnlyMe)
+    }
+
+    /// Convert distance from meters to miles
+    pub fn distance_in_miles(&self) -> f64 {
+        self.distance.miles()
     }
 
     /// Convert moving time from seconds to mins
-    pub fn moving_time_in_mins(&self) -> i64 {
-        self.moving_time / 60
+    pub fn moving_time_in_mins(&self) -> f64 {
+        self.moving_time.minutes()
     }
 
     /// Convert elapsed time  from seconds to mins
-    pub fn elapsed_time_in_mins(&self) -> i64 {
-        self.elapsed_time / 60
+    pub fn elapsed_time_in_mins(&self) -> f64 {
+        self.elapsed_time.minutes()
+    }
+
+    /// Returns the average pace over the moving time
+    pub fn pace(&self) -> Pace {
+        Pace::from_distance(self.distance, self.moving_time)
+    }
+
+    /// Returns the UTC start date as epoch seconds, or None if the date can't be parsed
+    pub fn start_date_epoch(&self) -> Option<i64> {
+        epoch_seconds(&self.start_date)
+    }
+
+    /// Returns the UTC start date
+    #[cfg(feature = "chrono")]
+    pub fn start_date_utc(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::models::datetime::parse_utc(&self.start_date)
+    }
+
+    /// Returns the start date on the wall clock where the activity took place
+    #[cfg(feature = "chrono")]
+    pub fn start_date_local_naive(&self) -> Result<chrono::NaiveDateTime, chrono::ParseError> {
+        crate::models::datetime::parse_local(&self.start_date_local)
+    }
+
+    /// Returns the IANA timezone of the activity, or None if chrono-tz does not know it
+    #[cfg(feature = "chrono")]
+    pub fn timezone_tz(&self) -> Option<chrono_tz::Tz> {
+        crate::models::datetime::parse_timezone(&self.timezone)
+    }
+
+    /// Returns the offset from UTC at the start of the activity
+    #[cfg(feature = "chrono")]
+    pub fn utc_offset_fixed(&self) -> Option<chrono::FixedOffset> {
+        crate::models::datetime::parse_utc_offset(self.utc_offset)
     }
 }
 
-/// Athlete resource
+/// Detailed representation of an activity, derefs to its summary fields
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedActivity
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Athlete {
-    pub id: i64,
-    pub resource_state: i32,
+pub struct DetailedActivity {
+    #[serde(flatten)]
+    pub summary: SummaryActivity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub calories: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embed_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gear: Option<SummaryGear>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub segment_efforts: Vec<DetailedSegmentEffort>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub splits_metric: Vec<Split>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub splits_standard: Vec<Split>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub laps: Laps,
 }
 
-/// Map Resource
+impl Deref for DetailedActivity {
+    type Target = SummaryActivity;
+
+    fn deref(&self) -> &SummaryActivity {
+        &self.summary
+    }
+}
+
+/// Split of a detailed activity, per kilometer in `splits_metric` and per mile in `splits_standard`
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-Split
 #[derive(Serialize, Deserialize, Debug)]
+pub struct Split {
+    pub split: i64,
+    pub distance: Distance,
+    pub elapsed_time: Duration,
+    pub moving_time: Duration,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elevation_difference: Option<Elevation>,
+    pub average_speed: Speed,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_grade_adjusted_speed: Option<Speed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_heartrate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pace_zone: Option<i64>,
+}
+
+#[cfg(feature = "chrono")]
+impl Lap {
+    /// Returns the UTC start date
+    pub fn start_date_utc(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::models::datetime::parse_utc(&self.start_date)
+    }
+
+    /// Returns the start date on the wall clock where the lap took place
+    pub fn start_date_local_naive(&self) -> Result<chrono::NaiveDateTime, chrono::ParseError> {
+        crate::models::datetime::parse_local(&self.start_date_local)
+    }
+}
+
+/// Map Resource, the detailed polyline is only returned for detailed activities and segments.
+/// The summary polyline is null for activities without GPS, such as manual or trainer activities
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Map {
-    pub id: String,
-    pub summary_polyline: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_polyline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub polyline: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+}
+
+impl Map {
+    /// Decodes the detailed polyline when present, otherwise the summary polyline.
+    /// Returns no points when the map has neither
+    pub fn decode_points(&self) -> Result<Vec<LatLng>, Box<dyn std::error::Error>> {
+        match self.polyline.as_ref().or(self.summary_polyline.as_ref()) {
+            Some(encoded) => polyline::decode(encoded),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Activity resource referenced by laps and efforts
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MetaActivity {
+    pub id: ActivityId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+}
+
+/// Collection of laps of an activity
+pub type Laps = Vec<Lap>;
+
+/// Lap of an activity
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-Lap
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Lap {
+    pub id: i64,
     pub resource_state: i64,
+    pub name: String,
+    pub activity: MetaActivity,
+    pub athlete: MetaAthlete,
+    pub elapsed_time: Duration,
+    pub moving_time: Duration,
+    pub start_date: String,
+    pub start_date_local: String,
+    pub distance: Distance,
+    pub start_index: i64,
+    pub end_index: i64,
+    pub total_elevation_gain: Elevation,
+    pub average_speed: Speed,
+    pub max_speed: Speed,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_cadence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_watts: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_watts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_heartrate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_heartrate: Option<f64>,
+    pub lap_index: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pace_zone: Option<i64>,
+}
+
+impl Lap {
+    /// Convert moving time from seconds to mins
+    pub fn moving_time_in_mins(&self) -> f64 {
+        self.moving_time.minutes()
+    }
+}
+
+/// Collection of heart rate and power zones of an activity
+pub type ActivityZones = Vec<ActivityZone>;
+
+/// Time spent in each zone of an activity, for either heart rate or power
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-ActivityZone
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityZone {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<i64>,
+    pub distribution_buckets: Vec<TimedZoneRange>,
+    #[serde(rename = "type")]
+    pub zone_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensor_based: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_zones: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
 }
 
-/// Types of activity and sport returned from the API
+impl ActivityZone {
+    /// Returns the total time in seconds spent across all the buckets
+    pub fn total_time(&self) -> i64 {
+        self.distribution_buckets
+            .iter()
+            .map(|bucket| bucket.time)
+            .sum()
+    }
+}
+
+/// A single zone bucket, max of -1 means the bucket is unbounded
 #[derive(Serialize, Deserialize, Debug)]
-pub enum ActivityType {
-    Rowing,
-    Run,
-    TrailRun,
-    VirtualRun,
-    Walk,
-    WeightTraining,
-    Ride,
-    MountainBikeRide,
-    GravelRide,
-    EMountainBikeRide,
-    Velomobile,
-    CrossCountrySki,
-    VirtualRide,
-    Kayak,
-    Swim,
-    Hike,
-    AlpineSki,
-    BackcountrySki,
-    Handcycle,
-    RockClimb,
-    Golf,
-    Skateboard,
-    Football,
-    Wheelchair,
-    Badminton,
-    Tennis,
-    Pickleball,
-    HIIT,
-    Squash,
-    Canoeing,
-    Crossfit,
-    EBikeRide,
-    Elliptical,
-    IceSkate,
-    InlineSkate,
-    Kayaking,
-    Pilates,
-    TableTennis,
-    Rqcquetball,
-    Volleyball,
-    Aerobics,
-    Anaerobics,
-    ArnoldSchwarzenegger,
-    Athletics,
-    BadmintonRacket,
-    Basketball,
-    Bicycle,
-    Kitesurf,
-    NordicSki,
-    RockClimbing,
-    RollerSki,
-    Snowboard,
-    Snowshoe,
-    StairStepper,
-    StandUpPaddling,
-    Surfing,
-    Windsurf,
-    Workout,
-    Yoga,
-    #[serde(other)]
-    Unknown,
+pub struct TimedZoneRange {
+    pub min: i64,
+    pub max: i64,
+    pub time: i64, // time in seconds
+}
+
+/// Comment left on an activity
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-Comment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Comment {
+    pub id: i64,
+    pub activity_id: ActivityId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    pub text: String,
+    pub athlete: SummaryAthlete,
+    pub created_at: String,
+}
+
+/// Collection of athletes who gave kudos to an activity
+pub type Kudoers = Vec<SummaryAthlete>;
+
+string_enum! {
+    /// Who can see an activity
+    pub enum Visibility {
+        Everyone => "everyone",
+        FollowersOnly => "followers_only",
+        OnlyMe => "only_me",
+    }
+}
+
+/// Workout type tagged by the athlete, the API sends it as a number
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "i64", into = "i64")]
+pub enum WorkoutType {
+    DefaultRun,
+    RunRace,
+    LongRun,
+    RunWorkout,
+    DefaultRide,
+    RideRace,
+    RideWorkout,
+    /// Value not known by this version of the crate, as returned by the API
+    Unknown(i64),
+}
+
+impl WorkoutType {
+    /// Returns true for runs and rides tagged as a race
+    pub fn is_race(&self) -> bool {
+        matches!(self, WorkoutType::RunRace | WorkoutType::RideRace)
+    }
+
+    /// Returns true for runs and rides tagged as a workout
+    pub fn is_workout(&self) -> bool {
+        matches!(self, WorkoutType::RunWorkout | WorkoutType::RideWorkout)
+    }
+}
+
+impl From<i64> for WorkoutType {
+    fn from(value: i64) -> WorkoutType {
+        match value {
+            0 => WorkoutType::DefaultRun,
+            1 => WorkoutType::RunRace,
+            2 => WorkoutType::LongRun,
+            3 => WorkoutType::RunWorkout,
+            10 => WorkoutType::DefaultRide,
+            11 => WorkoutType::RideRace,
+            12 => WorkoutType::RideWorkout,
+            other => WorkoutType::Unknown(other),
+        }
+    }
+}
+
+impl From<WorkoutType> for i64 {
+    fn from(workout_type: WorkoutType) -> i64 {
+        match workout_type {
+            WorkoutType::DefaultRun => 0,
+            WorkoutType::RunRace => 1,
+            WorkoutType::LongRun => 2,
+            WorkoutType::RunWorkout => 3,
+            WorkoutType::DefaultRide => 10,
+            WorkoutType::RideRace => 11,
+            WorkoutType::RideWorkout => 12,
+            WorkoutType::Unknown(value) => value,
+        }
+    }
+}
+
+string_enum! {
+    /// Sport type of an activity, the field to use since Strava added sport types
+    /// Documentation: https://developers.strava.com/docs/reference/#api-models-SportType
+    pub enum SportType {
+        AlpineSki => "AlpineSki",
+        BackcountrySki => "BackcountrySki",
+        Badminton => "Badminton",
+        Canoeing => "Canoeing",
+        Crossfit => "Crossfit",
+        EBikeRide => "EBikeRide",
+        Elliptical => "Elliptical",
+        EMountainBikeRide => "EMountainBikeRide",
+        Golf => "Golf",
+        GravelRide => "GravelRide",
+        Handcycle => "Handcycle",
+        HighIntensityIntervalTraining => "HighIntensityIntervalTraining",
+        Hike => "Hike",
+        IceSkate => "IceSkate",
+        InlineSkate => "InlineSkate",
+        Kayaking => "Kayaking",
+        Kitesurf => "Kitesurf",
+        MountainBikeRide => "MountainBikeRide",
+        NordicSki => "NordicSki",
+        Pickleball => "Pickleball",
+        Pilates => "Pilates",
+        Racquetball => "Racquetball",
+        Ride => "Ride",
+        RockClimbing => "RockClimbing",
+        RollerSki => "RollerSki",
+        Rowing => "Rowing",
+        Run => "Run",
+        Sail => "Sail",
+        Skateboard => "Skateboard",
+        Snowboard => "Snowboard",
+        Snowshoe => "Snowshoe",
+        Soccer => "Soccer",
+        Squash => "Squash",
+        StairStepper => "StairStepper",
+        StandUpPaddling => "StandUpPaddling",
+        Surfing => "Surfing",
+        Swim => "Swim",
+        TableTennis => "TableTennis",
+        Tennis => "Tennis",
+        TrailRun => "TrailRun",
+        Velomobile => "Velomobile",
+        VirtualRide => "VirtualRide",
+        VirtualRow => "VirtualRow",
+        VirtualRun => "VirtualRun",
+        Walk => "Walk",
+        WeightTraining => "WeightTraining",
+        Wheelchair => "Wheelchair",
+        Windsurf => "Windsurf",
+        Workout => "Workout",
+        Yoga => "Yoga",
+    }
+}
+
+impl SportType {
+    /// Returns true for runs, including trail and virtual runs
+    pub fn is_run(&self) -> bool {
+        matches!(
+            self,
+            SportType::Run | SportType::TrailRun | SportType::VirtualRun
+        )
+    }
+
+    /// Returns true for bike rides, including e-bike and virtual rides
+    pub fn is_ride(&self) -> bool {
+        matches!(
+            self,
+            SportType::Ride
+                | SportType::MountainBikeRide
+                | SportType::GravelRide
+                | SportType::EBikeRide
+                | SportType::EMountainBikeRide
+                | SportType::VirtualRide
+                | SportType::Velomobile
+        )
+    }
+
+    /// Returns true for activities recorded on a virtual platform like Zwift
+    pub fn is_virtual(&self) -> bool {
+        matches!(
+            self,
+            SportType::VirtualRide | SportType::VirtualRun | SportType::VirtualRow
+        )
+    }
+
+    /// Returns true for sports done on foot: runs, walks, hikes and snowshoeing
+    pub fn is_foot_sport(&self) -> bool {
+        self.is_run()
+            || matches!(
+                self,
+                SportType::Walk | SportType::Hike | SportType::Snowshoe
+            )
+    }
+}
+
+string_enum! {
+    /// Legacy activity type, returned alongside the sport type and used by segments
+    /// Documentation: https://developers.strava.com/docs/reference/#api-models-ActivityType
+    pub enum ActivityType {
+        AlpineSki => "AlpineSki",
+        BackcountrySki => "BackcountrySki",
+        Canoeing => "Canoeing",
+        Crossfit => "Crossfit",
+        EBikeRide => "EBikeRide",
+        Elliptical => "Elliptical",
+        Golf => "Golf",
+        Handcycle => "Handcycle",
+        Hike => "Hike",
+        IceSkate => "IceSkate",
+        InlineSkate => "InlineSkate",
+        Kayaking => "Kayaking",
+        Kitesurf => "Kitesurf",
+        NordicSki => "NordicSki",
+        Ride => "Ride",
+        RockClimbing => "RockClimbing",
+        RollerSki => "RollerSki",
+        Rowing => "Rowing",
+        Run => "Run",
+        Sail => "Sail",
+        Skateboard => "Skateboard",
+        Snowboard => "Snowboard",
+        Snowshoe => "Snowshoe",
+        Soccer => "Soccer",
+        StairStepper => "StairStepper",
+        StandUpPaddling => "StandUpPaddling",
+        Surfing => "Surfing",
+        Swim => "Swim",
+        Velomobile => "Velomobile",
+        VirtualRide => "VirtualRide",
+        VirtualRun => "VirtualRun",
+        Walk => "Walk",
+        WeightTraining => "WeightTraining",
+        Wheelchair => "Wheelchair",
+        Windsurf => "Windsurf",
+        Workout => "Workout",
+        Yoga => "Yoga",
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::units::UnitSystem;
 
     #[test]
-    fn map_unknown_activity_to_unknown() {
-        let activity = serde_json::from_str(r#""HighIntensityIntervalTraining""#);
-        assert!(matches!(activity, Ok(ActivityType::Unknown)));
+    fn unknown_sport_type_keeps_value() {
+        let sport: SportType = serde_json::from_str(r#""Padel""#).unwrap();
+        assert_eq!(sport, SportType::Unknown("Padel".to_string()));
+        assert_eq!(serde_json::to_string(&sport).unwrap(), r#""Padel""#);
+
+        let sport: SportType = serde_json::from_str(r#""VirtualRow""#).unwrap();
+        assert!(sport.is_virtual() && !sport.is_run());
+        assert!(SportType::TrailRun.is_foot_sport());
+        assert!(SportType::GravelRide.is_ride());
     }
 
     #[test]
@@ -227,9 +666,167 @@ mod tests {
             "total_photo_count":0,
             "has_kudoed":false
         }"#;
-        let activity: Result<ActivityElement, _> = serde_json::from_str(sample);
+        let activity: Result<SummaryActivity, _> = serde_json::from_str(sample);
 
         assert!(activity.is_ok());
-        assert_eq!(activity.unwrap().location_country, None);
+        let activity = activity.unwrap();
+        assert_eq!(activity.location_country, None);
+        assert_eq!(activity.visibility, Some(Visibility::OnlyMe));
+        assert_eq!(activity.location_city, None);
+        assert_eq!(activity.activity_type, Some(ActivityType::Workout));
+        assert_eq!(activity.sport_type, SportType::Workout);
+    }
+
+    #[test]
+    fn summary_activity_typed_fields_deserialize() {
+        let sample = r#"{
+            "resource_state":2,
+            "athlete":{"id":123,"resource_state":1},
+            "sport_type":"Run",
+            "workout_type":2,
+            "id":13997614564,
+            "start_date":"2025-03-26T23:27:40Z",
+            "start_date_local":"2025-03-27T08:27:40Z",
+            "timezone":"(GMT+09:00) Asia/Chita",
+            "utc_offset":32400.0,
+            "location_city":"Chita",
+            "location_state":"Zabaykalsky Krai",
+            "gear_id":"g12345",
+            "upload_id":14941550424
+        }"#;
+        let activity: SummaryActivity = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(activity.workout_type, Some(WorkoutType::LongRun));
+        assert!(!WorkoutType::LongRun.is_race() && WorkoutType::RideRace.is_race());
+        assert_eq!(activity.location_city.as_deref(), Some("Chita"));
+        assert_eq!(activity.location_state.as_deref(), Some("Zabaykalsky Krai"));
+        assert_eq!(activity.gear_id, Some(GearId::from("g12345")));
+        assert_eq!(activity.upload_id, Some(UploadId(14941550424)));
+
+        let unknown: WorkoutType = serde_json::from_str("99").unwrap();
+        assert_eq!(unknown, WorkoutType::Unknown(99));
+        assert_eq!(serde_json::to_string(&unknown).unwrap(), "99");
+    }
+
+    #[test]
+    fn summary_activity_measures_use_unit_types() {
+        let sample = r#"{
+            "resource_state":2,
+            "athlete":{"id":123,"resource_state":1},
+            "distance":10000.0,
+            "moving_time":2790,
+            "elapsed_time":2850,
+            "total_elevation_gain":85.0,
+            "sport_type":"Run",
+            "id":13997614563,
+            "start_date":"2025-03-26T23:27:40Z",
+            "start_date_local":"2025-03-27T08:27:40Z",
+            "timezone":"(GMT+09:00) Asia/Chita",
+            "utc_offset":32400.0,
+            "average_speed":3.584,
+            "max_speed":5.0,
+            "elev_high":62.4
+        }"#;
+        let activity: SummaryActivity = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(activity.distance.kilometers(), 10.0);
+        assert!((activity.distance_in_miles() - 6.2137).abs() < 0.001);
+        assert_eq!(activity.moving_time_in_mins(), 46.5);
+        assert_eq!(activity.elapsed_time.seconds(), 2850.0);
+        assert_eq!(activity.pace().format(UnitSystem::Metric), "4:39 /km");
+        assert!((activity.max_speed.kilometers_per_hour() - 18.0).abs() < 1e-9);
+        assert_eq!(activity.elev_high, Some(Elevation(62.4)));
+        assert_eq!(activity.elev_low, None);
+    }
+
+    #[test]
+    fn detailed_activity_of_other_athlete_deserializes() {
+        let sample = r#"{
+            "id":12345678987654321,
+            "resource_state":3,
+            "athlete":{"id":134815,"resource_state":1},
+            "name":"Happy Friday",
+            "distance":28099,
+            "moving_time":4207,
+            "elapsed_time":4410,
+            "total_elevation_gain":516,
+            "type":"Ride",
+            "sport_type":"MountainBikeRide",
+            "start_date":"2018-02-16T14:52:54Z",
+            "start_date_local":"2018-02-16T06:52:54Z",
+            "timezone":"(GMT-08:00) America/Los_Angeles",
+            "utc_offset":-28800,
+            "achievement_count":null,
+            "kudos_count":19,
+            "map":{"id":"a1410355832","polyline":"ki{eFvqfiVqAWQIGEEKAYJgBVqDJ{BHa@jAkNJw@Pw@V{APs@^aABQAOEQGKoJ_FuJkFqAo@{A}@sH{DiAs@Q]?WVy@`@oBt@_CB]KYMMkB{AQEI@WT{BlE{@zAQPI@ICsCqA_BcAeCmAaFmCqIoEcLeG}KcG}A}@cDaBiDsByAkAuBqBi@y@_@o@o@kB}BgIoA_EUkAMcACa@BeBBq@LaAJe@b@uA`@_AdBcD","resource_state":3,"summary_polyline":null},
+            "trainer":false,
+            "commute":false,
+            "manual":false,
+            "private":false,
+            "flagged":false,
+            "gear_id":"b12345678987654321",
+            "average_speed":6.679,
+            "max_speed":18.5,
+            "description":"Weekly group ride",
+            "calories":870.2,
+            "splits_metric":[{"distance":1001.5,"elapsed_time":141,"elevation_difference":4.4,"moving_time":141,"split":1,"average_speed":7.1,"pace_zone":0}],
+            "laps":null
+        }"#;
+        let activity: DetailedActivity = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(activity.sport_type, SportType::MountainBikeRide);
+        assert_eq!(activity.achievement_count, 0);
+        assert_eq!(activity.visibility, None);
+        assert_eq!(activity.description.as_deref(), Some("Weekly group ride"));
+        assert_eq!(activity.splits_metric[0].moving_time.seconds(), 141.0);
+        assert!(activity.laps.is_empty() && activity.map.polyline.is_some());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn unknown_timezone_does_not_fail_the_activity() {
+        let sample = r#"{
+            "resource_state":2,
+            "athlete":{"id":123,"resource_state":1},
+            "sport_type":"Run",
+            "id":13997614562,
+            "start_date":"2025-03-26T23:27:40Z",
+            "start_date_local":"2025-03-27T08:27:40Z",
+            "timezone":"(GMT+00:00) Etc/Unknown",
+            "utc_offset":32400.0
+        }"#;
+        let activity: SummaryActivity = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(activity.timezone_tz(), None);
+        assert_eq!(activity.start_date_utc().unwrap().timestamp(), 1743031660);
+        assert_eq!(
+            activity.start_date_local_naive().unwrap(),
+            activity.start_date_utc().unwrap().naive_utc() + chrono::Duration::hours(9)
+        );
+        assert_eq!(
+            activity.utc_offset_fixed().unwrap().local_minus_utc(),
+            32400
+        );
+    }
+
+    #[test]
+    fn activity_zones_deserialize_with_buckets() {
+        let sample = r#"[{
+            "score":42,
+            "distribution_buckets":[
+                {"min":0,"max":120,"time":300},
+                {"min":120,"max":150,"time":1200},
+                {"min":150,"max":-1,"time":60}
+            ],
+            "type":"heartrate",
+            "resource_state":3,
+            "sensor_based":true,
+            "custom_zones":false
+        }]"#;
+        let zones: ActivityZones = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(zones[0].zone_type, "heartrate");
+        assert_eq!(zones[0].distribution_buckets[2].max, -1);
+        assert_eq!(zones[0].total_time(), 1560);
     }
 }
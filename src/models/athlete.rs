@@ -2,105 +2,332 @@
 //! This model is used to represent the athlete object returned by the Strava API
 //! Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedAthlete
 //! Contains helper methods to convert units and get athlete information
+use crate::models::clubs::SummaryClub;
+use crate::models::gear::SummaryGear;
+use crate::models::ids::AthleteId;
+use crate::models::units::{Distance, Duration, Elevation, UnitSystem, Weight};
 use serde::{Deserialize, Serialize};
 
-/// Athlete fields returned from the API
+string_enum! {
+    /// Following state between the authenticated athlete and another athlete
+    pub enum FollowStatus {
+        Pending => "pending",
+        Accepted => "accepted",
+        Blocked => "blocked",
+    }
+}
+
+string_enum! {
+    /// Sex of an athlete
+    pub enum Sex {
+        Male => "M",
+        Female => "F",
+    }
+}
+
+string_enum! {
+    /// Unit the athlete displays measures in
+    pub enum MeasurementPreference {
+        Feet => "feet",
+        Meters => "meters",
+    }
+}
+
+string_enum! {
+    /// Format the athlete displays dates in, the values are strftime patterns
+    pub enum DatePreference {
+        MonthDayYear => "%m/%d/%Y",
+        DayMonthYear => "%d/%m/%Y",
+    }
+}
+
+/// Meta representation of an athlete, only its id
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-MetaAthlete
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MetaAthlete {
+    pub id: AthleteId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+}
+
+/// Summary representation of an athlete, returned for kudoers, comment authors and club members.
+/// The id is missing when the athlete is listed as a kudoer
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-SummaryAthlete
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummaryAthlete {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<AthleteId>,
+    pub resource_state: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub firstname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lastname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_medium: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub premium: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl SummaryAthlete {
+    /// Returns the full name of athlete using the firstname and lastname fields,
+    /// leaving out the ones the athlete's privacy settings hide
+    pub fn get_full_name(&self) -> String {
+        [&self.firstname, &self.lastname]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the UTC date the athlete signed up, None if not returned
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(
+        &self,
+    ) -> Option<Result<chrono::DateTime<chrono::Utc>, chrono::ParseError>> {
+        self.created_at
+            .as_deref()
+            .map(crate::models::datetime::parse_utc)
+    }
+
+    /// Returns the UTC date the athlete profile was last updated, None if not returned
+    #[cfg(feature = "chrono")]
+    pub fn updated_at_utc(
+        &self,
+    ) -> Option<Result<chrono::DateTime<chrono::Utc>, chrono::ParseError>> {
+        self.updated_at
+            .as_deref()
+            .map(crate::models::datetime::parse_utc)
+    }
+}
+
+/// Detailed representation of the authenticated athlete.
+/// Location, sex and weight are null until the athlete fills them in
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedAthlete
 #[derive(Serialize, Deserialize, Debug)]
-pub struct AthleteCollection {
-    pub id: f64,
+pub struct DetailedAthlete {
+    pub id: AthleteId,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
     pub resource_state: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub firstname: String,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub lastname: String,
-    pub city: String,
-    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
-    pub sex: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub premium: bool,
-    pub created_at: String,
-    pub updated_at: String,
-    pub badge_type_id: i64,
-    pub profile_medium: String,
-    pub profile: String,
-    pub friend: Option<serde_json::Value>,
-    pub follower: Option<serde_json::Value>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub summit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_type_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_medium: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub friend: Option<FollowStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower: Option<FollowStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub follower_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub friend_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mutual_friend_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub athlete_type: Option<i64>,
-    pub date_preference: Option<String>,
-    pub measurement_preference: Option<String>,
-    pub clubs: Option<Vec<Option<serde_json::Value>>>,
-    pub ftp: Option<serde_json::Value>,
-    pub weight: f64,
-    // weight in kg
-    pub bikes: Option<Vec<Gear>>,
-    pub shoes: Option<Vec<Gear>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_preference: Option<DatePreference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub measurement_preference: Option<MeasurementPreference>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub clubs: Vec<SummaryClub>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ftp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<Weight>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub bikes: Vec<SummaryGear>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub shoes: Vec<SummaryGear>,
 }
 
-impl AthleteCollection {
+/// Authenticated athlete returned by `get_athlete`
+pub type AthleteCollection = DetailedAthlete;
+
+impl DetailedAthlete {
     /// Returns the full name of athlete using the firstname and lastname fields
     pub fn get_full_name(&self) -> String {
         format!("{} {}", self.firstname, self.lastname)
     }
 
     /// Returns the id of the athlete
-    pub fn get_id(&self) -> f64 {
+    pub fn get_id(&self) -> AthleteId {
         self.id
     }
     /// Returns the username of the athlete
     pub fn get_username(&self) -> Option<String> {
         self.username.clone()
     }
-    /// Returns the weight in kg of the athlete
-    pub fn get_weight(&self) -> f64 {
-        self.weight
+    /// Returns the weight in kg of the athlete, None if not set
+    pub fn get_weight(&self) -> Option<f64> {
+        self.weight.map(|weight| weight.kilograms())
+    }
+    /// Converts the weight of the athlete into lb from kg, None if not set
+    pub fn weight_in_lbs(&self) -> Option<f64> {
+        self.weight.map(|weight| weight.pounds())
+    }
+    /// Returns the unit system the athlete chose to display measures in, metric by default
+    pub fn unit_system(&self) -> UnitSystem {
+        match self.measurement_preference {
+            Some(MeasurementPreference::Feet) => UnitSystem::Imperial,
+            _ => UnitSystem::Metric,
+        }
+    }
+    /// Returns the unit the athlete chose to display measures in, None if not returned
+    pub fn get_measurement_preference(&self) -> Option<MeasurementPreference> {
+        self.measurement_preference.clone()
     }
-    /// Converts the weight of the athlete into lb from kg
-    pub fn weight_in_lbs(&self) -> f64 {
-        self.weight * 2.20462
+    /// Returns the format the athlete chose to display dates in, None if not returned
+    pub fn get_date_preference(&self) -> Option<DatePreference> {
+        self.date_preference.clone()
     }
     /// Returns bool of premium status of athlete
     pub fn get_premium(&self) -> bool {
         self.premium
     }
-    /// Returns the sex of the athlete
-    pub fn get_sex(&self) -> String {
+    /// Returns the sex of the athlete, None if not set
+    pub fn get_sex(&self) -> Option<Sex> {
         self.sex.clone()
     }
-    /// Returns the city of the athlete
-    pub fn get_city(&self) -> String {
+    /// Returns the city of the athlete, None if not set
+    pub fn get_city(&self) -> Option<String> {
         self.city.clone()
     }
-    /// Returns the state of the athlete
-    pub fn get_state(&self) -> String {
+    /// Returns the state of the athlete, None if not set
+    pub fn get_state(&self) -> Option<String> {
         self.state.clone()
     }
-    /// Returns the shoe Vec of the athlete
-    pub fn get_shoes(&self) -> &Vec<Gear> {
-        self.shoes.as_ref().unwrap()
+    /// Returns the shoes of the athlete
+    pub fn get_shoes(&self) -> &[SummaryGear] {
+        &self.shoes
+    }
+    /// Returns the bikes of the athlete
+    pub fn get_bikes(&self) -> &[SummaryGear] {
+        &self.bikes
+    }
+
+    /// Returns the UTC date the athlete signed up, None if not returned
+    #[cfg(feature = "chrono")]
+    pub fn created_at_utc(
+        &self,
+    ) -> Option<Result<chrono::DateTime<chrono::Utc>, chrono::ParseError>> {
+        self.created_at
+            .as_deref()
+            .map(crate::models::datetime::parse_utc)
+    }
+
+    /// Returns the UTC date the athlete profile was last updated, None if not returned
+    #[cfg(feature = "chrono")]
+    pub fn updated_at_utc(
+        &self,
+    ) -> Option<Result<chrono::DateTime<chrono::Utc>, chrono::ParseError>> {
+        self.updated_at
+            .as_deref()
+            .map(crate::models::datetime::parse_utc)
     }
-    /// Returns the bike vec of the athlete
-    pub fn get_bikes(&self) -> &Vec<Gear> {
-        self.bikes.as_ref().unwrap()
+}
+
+/// Heart rate and power zones of the athlete
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-Zones
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Zones {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heart_rate: Option<HeartRateZoneRanges>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerZoneRanges>,
+}
+
+/// Heart rate zones, custom_zones is false when Strava computed them from the max heart rate
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HeartRateZoneRanges {
+    pub custom_zones: bool,
+    pub zones: Vec<ZoneRange>,
+}
+
+impl HeartRateZoneRanges {
+    /// Returns the index of the zone the heart rate in bpm falls in
+    pub fn zone_for(&self, heartrate: i64) -> Option<usize> {
+        zone_index(&self.zones, heartrate)
     }
 }
 
-/// Used for both shoes and bikes
+/// Power zones, set from the athlete's FTP
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Gear {
-    pub id: String,
-    pub primary: bool,
-    pub name: String,
-    pub resource_state: i64,
-    pub distance: f64, // distance in meters
+pub struct PowerZoneRanges {
+    pub zones: Vec<ZoneRange>,
+}
+
+impl PowerZoneRanges {
+    /// Returns the index of the zone the power in watts falls in
+    pub fn zone_for(&self, watts: i64) -> Option<usize> {
+        zone_index(&self.zones, watts)
+    }
+}
+
+/// A single zone, max of -1 means the zone is unbounded
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneRange {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl ZoneRange {
+    /// Returns true when the value falls in the zone, the max belongs to the next zone
+    pub fn contains(&self, value: i64) -> bool {
+        value >= self.min && (self.max == -1 || value < self.max)
+    }
+}
+
+/// Returns the index of the zone the value falls in
+fn zone_index(zones: &[ZoneRange], value: i64) -> Option<usize> {
+    zones.iter().position(|zone| zone.contains(value))
 }
 
 /// Athlete stats data
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AthleteStats {
     pub biggest_ride_distance: f64,
-    pub biggest_climb_elevation_gain: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biggest_climb_elevation_gain: Option<Elevation>,
     pub recent_ride_totals: Totals,
     pub all_ride_totals: Totals,
     pub recent_run_totals: Totals,
@@ -116,25 +343,136 @@ pub struct AthleteStats {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Totals {
     pub count: i64,
-    pub distance: f64,
-    // distance in meters
-    pub moving_time: f64,
-    // time in seconds
-    pub elapsed_time: f64,
-    pub elevation_gain: f64,
+    pub distance: Distance,
+    pub moving_time: Duration,
+    pub elapsed_time: Duration,
+    pub elevation_gain: Elevation,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub achievement_count: Option<i64>,
 }
 impl Totals {
-    /// Returns the distance in miles converting from km of the athlete
-    pub fn distance_in_miles(&self) -> i64 {
-        (self.distance * 0.000621371) as i64
+    /// Returns the distance in miles converting from meters
+    pub fn distance_in_miles(&self) -> f64 {
+        self.distance.miles()
     }
     /// Convert moving time from seconds to mins
-    pub fn moving_time_in_mins(&self) -> i64 {
-        (self.moving_time / 60.0) as i64
+    pub fn moving_time_in_mins(&self) -> f64 {
+        self.moving_time.minutes()
     }
     /// Convert elapsed time  from seconds to mins
-    pub fn elapsed_time_in_mins(&self) -> i64 {
-        (self.elapsed_time / 60.0) as i64
+    pub fn elapsed_time_in_mins(&self) -> f64 {
+        self.elapsed_time.minutes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detailed_athlete_allows_missing_profile_fields() {
+        let sample = r#"{
+            "id":1234567890987654321,
+            "username":null,
+            "resource_state":3,
+            "firstname":"Marianne",
+            "lastname":"T.",
+            "city":null,
+            "state":null,
+            "country":null,
+            "sex":null,
+            "premium":true,
+            "summit":true,
+            "created_at":"2017-11-14T02:30:05Z",
+            "updated_at":"2018-02-06T19:32:20Z",
+            "badge_type_id":4,
+            "profile_medium":"https://xxxxxx.cloudfront.net/pictures/athletes/123456789/123456789/2/medium.jpg",
+            "profile":"https://xxxxx.cloudfront.net/pictures/athletes/123456789/123456789/2/large.jpg",
+            "friend":null,
+            "follower":null,
+            "follower_count":5,
+            "friend_count":5,
+            "measurement_preference":"feet",
+            "ftp":null,
+            "weight":null,
+            "clubs":null,
+            "bikes":[{"id":"b12345678987655","primary":true,"name":"EMC","resource_state":2,"distance":0}],
+            "shoes":null
+        }"#;
+        let athlete: DetailedAthlete = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(athlete.get_id(), AthleteId(1234567890987654321));
+        assert_eq!(athlete.get_city(), None);
+        assert_eq!(athlete.get_weight(), None);
+        assert_eq!(athlete.get_sex(), None);
+        assert_eq!(athlete.unit_system(), UnitSystem::Imperial);
+        assert_eq!(athlete.get_bikes()[0].name.as_deref(), Some("EMC"));
+        assert!(athlete.get_shoes().is_empty() && athlete.clubs.is_empty());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn athlete_dates_parse_into_chrono_types() {
+        let sample = r#"{
+            "id":134815,
+            "resource_state":3,
+            "created_at":"2017-11-14T02:30:05Z",
+            "updated_at":"2018-02-06T19:32:20Z"
+        }"#;
+        let detailed: DetailedAthlete = serde_json::from_str(sample).unwrap();
+        let summary: SummaryAthlete = serde_json::from_str(sample).unwrap();
+
+        let created_at = detailed.created_at_utc().unwrap().unwrap();
+        assert_eq!(created_at.timestamp(), 1510626605);
+        assert_eq!(summary.created_at_utc().unwrap().unwrap(), created_at);
+        assert_eq!(
+            detailed.updated_at_utc().unwrap().unwrap().timestamp(),
+            1517945540
+        );
+
+        let summary: SummaryAthlete =
+            serde_json::from_str(r#"{"id":134815,"resource_state":1}"#).unwrap();
+        assert!(summary.created_at_utc().is_none() && summary.updated_at_utc().is_none());
+    }
+
+    #[test]
+    fn athlete_enums_keep_unknown_values() {
+        let sex: Sex = serde_json::from_str(r#""F""#).unwrap();
+        assert_eq!(sex, Sex::Female);
+        let sex: Sex = serde_json::from_str(r#""X""#).unwrap();
+        assert_eq!(sex, Sex::Unknown("X".to_string()));
+        assert_eq!(serde_json::to_string(&sex).unwrap(), r#""X""#);
+
+        let preference: MeasurementPreference = serde_json::from_str(r#""meters""#).unwrap();
+        assert_eq!(preference, MeasurementPreference::Meters);
+        let preference: MeasurementPreference = serde_json::from_str(r#""yards""#).unwrap();
+        assert_eq!(
+            preference,
+            MeasurementPreference::Unknown("yards".to_string())
+        );
+
+        let preference: DatePreference = serde_json::from_str(r#""%d/%m/%Y""#).unwrap();
+        assert_eq!(preference, DatePreference::DayMonthYear);
+        let preference: DatePreference = serde_json::from_str(r#""%Y-%m-%d""#).unwrap();
+        assert_eq!(preference, DatePreference::Unknown("%Y-%m-%d".to_string()));
+    }
+
+    #[test]
+    fn zones_find_zone_of_value() {
+        let sample = r#"{
+            "heart_rate":{"custom_zones":false,"zones":[
+                {"min":0,"max":123},{"min":123,"max":153},{"min":153,"max":169},
+                {"min":169,"max":184},{"min":184,"max":-1}
+            ]},
+            "power":{"zones":[{"min":0,"max":150},{"min":150,"max":-1}]}
+        }"#;
+        let zones: Zones = serde_json::from_str(sample).unwrap();
+        let heart_rate = zones.heart_rate.unwrap();
+
+        assert!(!heart_rate.custom_zones);
+        assert_eq!(heart_rate.zone_for(122), Some(0));
+        assert_eq!(heart_rate.zone_for(153), Some(2));
+        assert_eq!(heart_rate.zone_for(201), Some(4));
+        assert_eq!(zones.power.unwrap().zone_for(300), Some(1));
     }
 }
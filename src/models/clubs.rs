@@ -1,34 +1,110 @@
 //! #  Clubs Model
 //! This model is used to represent the club object returned by the Strava API
 //! Documentation: https://developers.strava.com/docs/reference/#api-Clubs
+use crate::models::activities::{ActivityType, SportType, WorkoutType};
+use crate::models::ids::ClubId;
+use crate::models::units::{Distance, Duration, Elevation};
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+string_enum! {
+    /// Sport a club is dedicated to
+    pub enum ClubSportType {
+        Cycling => "cycling",
+        Running => "running",
+        Triathlon => "triathlon",
+        Other => "other",
+    }
+}
+
+string_enum! {
+    /// Membership of an athlete in a club, pending until a private club accepts the request
+    pub enum Membership {
+        Member => "member",
+        Pending => "pending",
+    }
+}
 
 /// Collection of clubs
-pub type ClubCollection = Vec<Club>;
+pub type ClubCollection = Vec<SummaryClub>;
 
-/// Club fields returned from the API
+/// Meta representation of a club
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-MetaClub
 #[derive(Debug, Serialize, Deserialize)]
-pub struct Club {
-    pub id: i64,
+pub struct MetaClub {
+    pub id: ClubId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Club summary, as listed in the clubs of the authenticated athlete
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-SummaryClub
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryClub {
+    pub id: ClubId,
     pub resource_state: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub name: String,
-    pub profile_medium: String,
-    pub profile: String,
-    pub cover_photo: String,
-    pub cover_photo_small: String,
-    pub activity_types: Vec<Option<serde_json::Value>>,
-    pub activity_types_icon: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_medium: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_photo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_photo_small: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub activity_types: Vec<ActivityType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_types_icon: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub dimensions: Vec<String>,
-    pub sport_type: String,
-    pub localized_sport_type: String,
-    pub city: String,
-    pub state: String,
-    pub country: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sport_type: Option<ClubSportType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub localized_sport_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub private: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub member_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub featured: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub verified: bool,
-    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Detailed representation of a club, derefs to its summary fields
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedClub
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetailedClub {
+    #[serde(flatten)]
+    pub summary: SummaryClub,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub membership: Option<Membership>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub admin: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub owner: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub following_count: Option<i64>,
+}
+
+impl Deref for DetailedClub {
+    type Target = SummaryClub;
+
+    fn deref(&self) -> &SummaryClub {
+        &self.summary
+    }
 }
 
 /// Collection of members of the club
@@ -38,10 +114,15 @@ pub type ClubMembers = Vec<Member>;
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Member {
     pub resource_state: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub firstname: String,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub lastname: String,
-    pub membership: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub membership: Option<Membership>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub admin: bool,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub owner: bool,
 }
 
@@ -65,14 +146,15 @@ pub struct ClubActivity {
     pub resource_state: i64,
     pub athlete: Athlete,
     pub name: String,
-    pub distance: f64,
-    pub moving_time: i64,
-    pub elapsed_time: i64,
-    pub total_elevation_gain: f64,
+    pub distance: Distance,
+    pub moving_time: Duration,
+    pub elapsed_time: Duration,
+    pub total_elevation_gain: Elevation,
     #[serde(rename = "type")]
-    pub activity_type: String,
-    pub sport_type: String,
-    pub workout_type: Option<serde_json::Value>,
+    pub activity_type: ActivityType,
+    pub sport_type: SportType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workout_type: Option<WorkoutType>,
 }
 
 /// Athlete details of the club activity
@@ -82,3 +164,32 @@ pub struct Athlete {
     pub firstname: String,
     pub lastname: String,
 }
+
+/// Membership state returned after joining or leaving a club
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClubMembershipResponse {
+    pub success: bool,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub membership: Option<Membership>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn club_membership_response_deserializes() {
+        let joined: ClubMembershipResponse =
+            serde_json::from_str(r#"{"success":true,"active":false,"membership":"pending"}"#)
+                .unwrap();
+        let left: ClubMembershipResponse =
+            serde_json::from_str(r#"{"success":true,"active":false}"#).unwrap();
+
+        assert!(joined.success);
+        assert!(!joined.active);
+        assert_eq!(joined.membership, Some(Membership::Pending));
+        assert!(left.success);
+        assert_eq!(left.membership, None);
+    }
+}
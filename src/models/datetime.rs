@@ -0,0 +1,66 @@
+//! # Date-time parsing
+//! The models keep timestamps as the strings and numbers returned by the API:
+//! * UTC instants in the `2025-03-26T23:27:40Z` format, e.g. `start_date`
+//! * local wall clock times in the `2025-03-27T08:27:40Z` format, the `Z` does not mean UTC,
+//!   e.g. `start_date_local`
+//! * timezones in the `(GMT+09:00) Asia/Chita` format
+//! * offsets from UTC in seconds
+//!
+//! With the `chrono` feature the functions below parse them into chrono types, and the models
+//! gain accessors using them. The field types are the same with or without the feature.
+
+/// Returns the epoch seconds of a UTC instant, or None if it can't be parsed
+pub fn epoch_seconds(date: &str) -> Option<i64> {
+    crate::util::timestamp::parse_utc_timestamp(date)
+}
+
+/// Parses a UTC instant
+#[cfg(feature = "chrono")]
+pub fn parse_utc(date: &str) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+    chrono::DateTime::parse_from_rfc3339(date).map(|date| date.with_timezone(&chrono::Utc))
+}
+
+/// Parses a local wall clock time, ignoring the trailing `Z` the API adds although it is not UTC
+#[cfg(feature = "chrono")]
+pub fn parse_local(date: &str) -> Result<chrono::NaiveDateTime, chrono::ParseError> {
+    let date = date.strip_suffix('Z').unwrap_or(date);
+    chrono::NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S")
+}
+
+/// Parses the `(GMT+09:00) Asia/Chita` format, or a plain IANA name.
+/// Returns None for a timezone unknown to chrono-tz, so one odd label does not fail a whole page
+#[cfg(feature = "chrono")]
+pub fn parse_timezone(label: &str) -> Option<chrono_tz::Tz> {
+    let name = match label.split_once(") ") {
+        Some((_, name)) => name,
+        None => label,
+    };
+    name.trim().parse().ok()
+}
+
+/// Converts an offset in seconds, or None if it is out of range
+#[cfg(feature = "chrono")]
+pub fn parse_utc_offset(seconds: f64) -> Option<chrono::FixedOffset> {
+    chrono::FixedOffset::east_opt(seconds as i32)
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strava_timestamps_parse_into_chrono_types() {
+        let start_date = parse_utc("2025-03-26T23:27:40Z").unwrap();
+        let start_date_local = parse_local("2025-03-27T08:27:40Z").unwrap();
+        let timezone = parse_timezone("(GMT+09:00) Asia/Chita").unwrap();
+
+        assert_eq!(start_date.timestamp(), 1743031660);
+        assert_eq!(timezone, chrono_tz::Asia::Chita);
+        assert_eq!(parse_utc_offset(32400.0).unwrap().local_minus_utc(), 32400);
+        assert_eq!(
+            start_date.with_timezone(&timezone).naive_local(),
+            start_date_local
+        );
+        assert_eq!(parse_timezone("(GMT+00:00) Etc/Unknown"), None);
+    }
+}
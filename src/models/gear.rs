@@ -1,22 +1,198 @@
 //! Gear model
 //! This model is used to represent the gear object returned by the Strava API
 //! Documentation: https://developers.strava.com/docs/reference/#api-models-Gear
+//! Bikes and shoes share the same model, told apart by the prefix of their id
 
+use crate::models::ids::GearId;
+use crate::models::units::{Distance, Weight};
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
 
-/// Gear fields returned from the API
+/// Kind of gear, bike ids start with `b` and shoe ids with `g`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GearKind {
+    Bike,
+    Shoes,
+    Unknown,
+}
+
+impl GearId {
+    /// Returns the kind of gear the id refers to
+    pub fn kind(&self) -> GearKind {
+        match self.0.chars().next() {
+            Some('b') => GearKind::Bike,
+            Some('g') => GearKind::Shoes,
+            _ => GearKind::Unknown,
+        }
+    }
+}
+
+/// Frame type of a bike, the API sends it as a number
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "i64", into = "i64")]
+pub enum FrameType {
+    MountainBike,
+    Cross,
+    Road,
+    TimeTrial,
+    Gravel,
+    /// Value not known by this version of the crate, as returned by the API
+    Unknown(i64),
+}
+
+impl From<i64> for FrameType {
+    fn from(value: i64) -> FrameType {
+        match value {
+            1 => FrameType::MountainBike,
+            2 => FrameType::Cross,
+            3 => FrameType::Road,
+            4 => FrameType::TimeTrial,
+            5 => FrameType::Gravel,
+            other => FrameType::Unknown(other),
+        }
+    }
+}
+
+impl From<FrameType> for i64 {
+    fn from(frame_type: FrameType) -> i64 {
+        match frame_type {
+            FrameType::MountainBike => 1,
+            FrameType::Cross => 2,
+            FrameType::Road => 3,
+            FrameType::TimeTrial => 4,
+            FrameType::Gravel => 5,
+            FrameType::Unknown(value) => value,
+        }
+    }
+}
+
+/// Summary representation of a bike or shoes, listed in the athlete's bikes and shoes
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-SummaryGear
 #[derive(Debug, Serialize, Deserialize)]
-pub struct GearCollection {
-    pub id: String,
+pub struct SummaryGear {
+    pub id: GearId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub primary: bool,
-    pub name: String,
-    pub nickname: String,
-    pub resource_state: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
     pub retired: bool,
-    pub distance: i64,
-    pub converted_distance: f64,
-    pub brand_name: String,
-    pub model_name: String,
-    pub description: Option<serde_json::Value>,
-    pub notification_distance: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub distance: Distance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub converted_distance: Option<f64>,
+}
+
+impl SummaryGear {
+    /// Returns whether the gear is a bike or shoes
+    pub fn kind(&self) -> GearKind {
+        self.id.kind()
+    }
+}
+
+/// Detailed representation of a bike or shoes, derefs to its summary fields.
+/// Frame type and weight are only returned for bikes
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedGear
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetailedGear {
+    #[serde(flatten)]
+    pub summary: SummaryGear,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub brand_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame_type: Option<FrameType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<Weight>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_distance: Option<i64>,
+}
+
+impl Deref for DetailedGear {
+    type Target = SummaryGear;
+
+    fn deref(&self) -> &SummaryGear {
+        &self.summary
+    }
+}
+
+/// Gear returned by `get_gear`
+pub type GearCollection = DetailedGear;
+
+/// Every bike and pair of shoes of an athlete with their details, retired gear included
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct GearInventory {
+    pub bikes: Vec<DetailedGear>,
+    pub shoes: Vec<DetailedGear>,
+}
+
+impl GearInventory {
+    /// Returns the bikes and shoes, bikes first
+    pub fn all(&self) -> impl Iterator<Item = &DetailedGear> {
+        self.bikes.iter().chain(self.shoes.iter())
+    }
+
+    /// Returns the gear still in use
+    pub fn active(&self) -> impl Iterator<Item = &DetailedGear> {
+        self.all().filter(|gear| !gear.retired)
+    }
+
+    /// Returns the retired gear
+    pub fn retired(&self) -> impl Iterator<Item = &DetailedGear> {
+        self.all().filter(|gear| gear.retired)
+    }
+
+    /// Returns the gear with the id
+    pub fn get(&self, gear_id: &GearId) -> Option<&DetailedGear> {
+        self.all().find(|gear| &gear.id == gear_id)
+    }
+
+    /// Returns the distance covered with all the gear
+    pub fn total_distance(&self) -> Distance {
+        Distance(self.all().map(|gear| gear.distance.meters()).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detailed_gear_deserializes_bike_fields() {
+        let sample = r#"{
+            "id":"b1231",
+            "primary":false,
+            "resource_state":3,
+            "distance":388206.5,
+            "brand_name":"BMC",
+            "model_name":"Teammachine",
+            "frame_type":3,
+            "weight":7.5,
+            "description":"My Bike.",
+            "retired":true
+        }"#;
+        let bike: DetailedGear = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(bike.kind(), GearKind::Bike);
+        assert_eq!(bike.frame_type, Some(FrameType::Road));
+        assert_eq!(bike.weight, Some(Weight(7.5)));
+        assert_eq!(bike.name, None);
+        assert_eq!(GearId::from("g4456").kind(), GearKind::Shoes);
+
+        let inventory = GearInventory {
+            bikes: vec![bike],
+            shoes: Vec::new(),
+        };
+        assert_eq!(inventory.retired().count(), 1);
+        assert_eq!(inventory.active().count(), 0);
+        assert_eq!(inventory.total_distance(), Distance(388206.5));
+        assert!(inventory.get(&GearId::from("b1231")).is_some());
+    }
 }
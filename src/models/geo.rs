@@ -0,0 +1,127 @@
+//! # Geo model
+//! Coordinates and bounding boxes used by maps, streams and the segment explorer
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+
+/// A point in decimal degrees, serialized as a `[lat, lng]` pair like the API does
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(from = "[f64; 2]", into = "[f64; 2]")]
+pub struct LatLng {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+/// Mean radius of the earth in meters
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+impl LatLng {
+    pub fn new(lat: f64, lng: f64) -> LatLng {
+        LatLng { lat, lng }
+    }
+
+    /// Returns the great circle distance in meters to another point, using the haversine formula
+    pub fn distance_to(&self, other: LatLng) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lng = (other.lng - self.lng).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
+impl From<[f64; 2]> for LatLng {
+    fn from(pair: [f64; 2]) -> LatLng {
+        LatLng::new(pair[0], pair[1])
+    }
+}
+
+impl From<LatLng> for [f64; 2] {
+    fn from(point: LatLng) -> [f64; 2] {
+        [point.lat, point.lng]
+    }
+}
+
+/// Rectangle of coordinates delimited by its south west and north east corners
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub south_west: LatLng,
+    pub north_east: LatLng,
+}
+
+impl BoundingBox {
+    /// Create a bounding box from its corners.
+    /// Returns an error if `south_west` is north or east of `north_east`, boxes crossing the
+    /// antimeridian are not supported
+    pub fn new(south_west: LatLng, north_east: LatLng) -> Result<BoundingBox, Box<dyn Error>> {
+        if south_west.lat > north_east.lat || south_west.lng > north_east.lng {
+            return Err(format!(
+                "South west corner {:?} is north or east of the north east corner {:?}",
+                south_west, north_east
+            )
+            .into());
+        }
+        Ok(BoundingBox {
+            south_west,
+            north_east,
+        })
+    }
+
+    /// Returns the smallest box containing all the points, or None if there are no points
+    pub fn around(points: &[LatLng]) -> Option<BoundingBox> {
+        let first = *points.first()?;
+        Some(points.iter().fold(
+            BoundingBox {
+                south_west: first,
+                north_east: first,
+            },
+            |bounds, point| BoundingBox {
+                south_west: LatLng::new(
+                    bounds.south_west.lat.min(point.lat),
+                    bounds.south_west.lng.min(point.lng),
+                ),
+                north_east: LatLng::new(
+                    bounds.north_east.lat.max(point.lat),
+                    bounds.north_east.lng.max(point.lng),
+                ),
+            },
+        ))
+    }
+
+    /// Returns true when the point lies inside or on the edge of the box
+    pub fn contains(&self, point: LatLng) -> bool {
+        (self.south_west.lat..=self.north_east.lat).contains(&point.lat)
+            && (self.south_west.lng..=self.north_east.lng).contains(&point.lng)
+    }
+}
+
+/// Formats the box as `sw_lat,sw_lng,ne_lat,ne_lng`, the format of the API `bounds` parameter
+impl fmt::Display for BoundingBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.south_west.lat, self.south_west.lng, self.north_east.lat, self.north_east.lng
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box_rejects_inverted_corners() {
+        let south_west = LatLng::new(37.78, -122.52);
+        let north_east = LatLng::new(37.86, -122.44);
+        let bounds = BoundingBox::new(south_west, north_east).unwrap();
+
+        assert_eq!(bounds.to_string(), "37.78,-122.52,37.86,-122.44");
+        assert!(bounds.contains(LatLng::new(37.8, -122.48)));
+        assert!(BoundingBox::new(north_east, south_west).is_err());
+        assert!(
+            BoundingBox::new(LatLng::new(37.78, -122.44), LatLng::new(37.86, -122.52)).is_err()
+        );
+        assert_eq!(BoundingBox::around(&[north_east, south_west]), Some(bounds));
+    }
+}
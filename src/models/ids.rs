@@ -0,0 +1,129 @@
+//! # Ids model
+//! Typed ids of the Strava resources, so an id of one resource can't be passed where another is expected.
+//! Numeric ids wrap an `i64`, gear ids wrap the string returned by the API, e.g. `b1234567`.
+//! All ids serialize like the raw value and display as used in the API paths.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// Declares a numeric id
+macro_rules! numeric_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        #[serde(transparent)]
+        pub struct $name(pub i64);
+
+        impl From<i64> for $name {
+            fn from(id: i64) -> $name {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for i64 {
+            fn from(id: $name) -> i64 {
+                id.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(id: &str) -> Result<$name, ParseIntError> {
+                id.parse().map($name)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+numeric_id!(
+    /// Id of an activity
+    ActivityId
+);
+numeric_id!(
+    /// Id of an athlete
+    AthleteId
+);
+numeric_id!(
+    /// Id of a club
+    ClubId
+);
+numeric_id!(
+    /// Id of a segment
+    SegmentId
+);
+numeric_id!(
+    /// Id of an effort on a segment
+    SegmentEffortId
+);
+numeric_id!(
+    /// Id of a route
+    RouteId
+);
+numeric_id!(
+    /// Id of an upload, different from the id of the activity it creates
+    UploadId
+);
+numeric_id!(
+    /// Id of the webhook push subscription of the application
+    SubscriptionId
+);
+
+/// Id of a bike or shoes, prefixed with `b` for bikes and `g` for shoes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct GearId(pub String);
+
+impl GearId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for GearId {
+    fn from(id: &str) -> GearId {
+        GearId(id.to_string())
+    }
+}
+
+impl From<String> for GearId {
+    fn from(id: String) -> GearId {
+        GearId(id)
+    }
+}
+
+impl AsRef<str> for GearId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for GearId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_serialize_as_raw_values() {
+        let id: ActivityId = serde_json::from_str("12345678987654321").unwrap();
+        assert_eq!(id, ActivityId(12345678987654321));
+        assert_eq!(id.to_string(), "12345678987654321");
+        assert_eq!("42".parse::<ClubId>().unwrap(), ClubId(42));
+
+        let gear: GearId = serde_json::from_str(r#""b12345""#).unwrap();
+        assert_eq!(gear.as_str(), "b12345");
+        assert_eq!(serde_json::to_string(&gear).unwrap(), r#""b12345""#);
+    }
+}
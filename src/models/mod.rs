@@ -6,9 +6,98 @@ pub use self::athlete::AthleteCollection;
 pub use self::athlete::AthleteStats;
 pub use self::clubs::ClubCollection;
 pub use self::gear::GearCollection;
+pub use self::routes::RouteCollection;
+pub use self::segments::DetailedSegment;
+pub use self::segments::SegmentCollection;
+pub use self::segments::SegmentEffortCollection;
+pub use self::streams::StreamSet;
+pub use self::uploads::Upload;
+pub use self::webhooks::Subscription;
+pub use self::webhooks::WebhookEvent;
+
+/// Deserializes a null value as the default of the type, used on fields the API omits or nulls
+/// depending on the resource state and privacy settings. Combine with `#[serde(default)]`.
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + serde::Deserialize<'de>,
+{
+    use serde::Deserialize;
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Declares an enum of the string values returned by the API.
+/// Values not known by this version of the crate are kept in an `Unknown` variant
+/// so they survive a serialization round trip.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// Value not known by this version of the crate, as returned by the API
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Returns the value used by the API
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> $name {
+                match value {
+                    $($value => $name::$variant,)*
+                    _ => $name::Unknown(value.to_string()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> $name {
+                $name::from(value.as_str())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                match value {
+                    $name::Unknown(value) => value,
+                    known => known.as_str().to_string(),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
 
 pub mod athlete;
 
 pub mod activities;
 pub mod clubs;
+pub mod datetime;
 pub mod gear;
+pub mod geo;
+pub mod ids;
+pub mod routes;
+pub mod segments;
+pub mod streams;
+pub mod units;
+pub mod uploads;
+pub mod webhooks;
@@ -0,0 +1,115 @@
+//! # Routes model
+//! This model is used to represent the route object returned by the Strava API
+//! Documentation: https://developers.strava.com/docs/reference/#api-models-Route
+use crate::models::activities::Map;
+use crate::models::athlete::SummaryAthlete;
+use crate::models::ids::RouteId;
+use crate::models::segments::SummarySegment;
+use crate::models::units::{Distance, Duration, Elevation};
+use serde::{Deserialize, Serialize};
+
+/// Collection of routes
+pub type RouteCollection = Vec<Route>;
+
+/// Route fields returned from the API
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Route {
+    pub id: RouteId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_str: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub athlete: Option<SummaryAthlete>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub distance: Distance,
+    pub elevation_gain: Elevation,
+    pub map: Map,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map_urls: Option<MapUrls>,
+    pub private: bool,
+    pub starred: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(rename = "type")]
+    pub route_type: i64,
+    pub sub_type: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_moving_time: Option<Duration>,
+    #[serde(default)]
+    pub segments: Vec<SummarySegment>,
+}
+
+impl Route {
+    /// Returns true when the route was created for riding
+    pub fn is_ride(&self) -> bool {
+        self.route_type == 1
+    }
+
+    /// Returns true when the route was created for running
+    pub fn is_run(&self) -> bool {
+        self.route_type == 2
+    }
+
+    /// Returns the id as a string, using id_str when the API sent it
+    pub fn get_id(&self) -> String {
+        self.id_str.clone().unwrap_or_else(|| self.id.to_string())
+    }
+}
+
+/// Image urls of the route map
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MapUrls {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retina_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dark_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dark_retina_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::units::UnitSystem;
+
+    #[test]
+    fn route_deserializes_with_large_id() {
+        let sample = r#"{
+            "athlete":{"id":134815,"resource_state":2,"firstname":"Marianne","lastname":"T."},
+            "description":"Morning loop",
+            "distance":16932.1,
+            "elevation_gain":195.0,
+            "id":3344417779237838848,
+            "id_str":"3344417779237838848",
+            "map":{"id":"r3344417779237838848","summary_polyline":"_p~iF~ps|U_ulLnnqC_mqNvxq`@","resource_state":2},
+            "map_urls":{"url":"https://d3o5xota0a1fcr.cloudfront.net/v6/maps/route.png"},
+            "name":"Presidio Loop",
+            "private":false,
+            "starred":true,
+            "timestamp":1739915186,
+            "type":2,
+            "sub_type":4,
+            "created_at":"2025-02-18T21:46:26Z",
+            "updated_at":"2025-02-18T21:46:26Z",
+            "estimated_moving_time":5925
+        }"#;
+        let route: Route = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(route.id, RouteId(3344417779237838848));
+        assert_eq!(route.get_id(), "3344417779237838848");
+        assert!(route.is_run() && !route.is_ride());
+        assert_eq!(route.athlete.unwrap().get_full_name(), "Marianne T.");
+        assert_eq!(route.map.decode_points().unwrap().len(), 3);
+        assert!(route.segments.is_empty());
+        assert_eq!(route.distance.format(UnitSystem::Imperial), "10.5 mi");
+        assert_eq!(route.elevation_gain.format(UnitSystem::Metric), "195 m");
+        assert_eq!(route.estimated_moving_time.unwrap().format(), "1:38:45");
+    }
+}
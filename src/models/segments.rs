@@ -0,0 +1,421 @@
+//! # Segments model
+//! This model is used to represent the segment objects returned by the Strava API
+//! Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedSegment
+//! Contains helper methods to get the athlete's personal record on a segment
+//! Segment efforts documentation: https://developers.strava.com/docs/reference/#api-models-DetailedSegmentEffort
+use crate::models::activities::{ActivityType, Map, MetaActivity};
+use crate::models::athlete::MetaAthlete;
+use crate::models::geo::LatLng;
+use crate::models::ids::{ActivityId, AthleteId, SegmentEffortId, SegmentId};
+use crate::models::units::{Distance, Duration, Elevation, Speed};
+use crate::util::polyline;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::ops::Deref;
+
+/// Collection of summary segments, used for starred segments
+pub type SegmentCollection = Vec<SummarySegment>;
+
+/// Summary representation of a segment, returned for starred segments and segment efforts
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-SummarySegment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummarySegment {
+    pub id: SegmentId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub name: String,
+    pub activity_type: ActivityType,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub distance: Distance,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub average_grade: f64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub maximum_grade: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elevation_high: Option<Elevation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elevation_low: Option<Elevation>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub start_latlng: Vec<f64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub end_latlng: Vec<f64>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub climb_category: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub private: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hazardous: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starred: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub athlete_pr_effort: Option<SummaryPRSegmentEffort>,
+    /// Always None in a `DetailedSegment`, which reads the key into its own detailed stats,
+    /// and left out when None so the key is only serialized once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub athlete_segment_stats: Option<SummarySegmentEffort>,
+}
+
+impl SummarySegment {
+    /// Returns the athlete's best elapsed time on the segment in seconds
+    pub fn get_pr_time(&self) -> Option<i64> {
+        self.pr_time.or_else(|| {
+            self.athlete_pr_effort
+                .as_ref()
+                .and_then(|effort| effort.pr_elapsed_time)
+        })
+    }
+}
+
+/// Detailed representation of a segment, derefs to its summary fields.
+/// The athlete's stats use the detailed `athlete_segment_stats` format
+/// Documentation: https://developers.strava.com/docs/reference/#api-models-DetailedSegment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DetailedSegment {
+    #[serde(flatten)]
+    pub summary: SummarySegment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_elevation_gain: Option<Elevation>,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub map: Map,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub effort_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub athlete_count: i64,
+    #[serde(default, deserialize_with = "crate::models::null_as_default")]
+    pub star_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub athlete_segment_stats: Option<AthleteSegmentStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xoms: Option<Xoms>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_legend: Option<LocalLegend>,
+}
+
+impl Deref for DetailedSegment {
+    type Target = SummarySegment;
+
+    fn deref(&self) -> &SummarySegment {
+        &self.summary
+    }
+}
+
+impl DetailedSegment {
+    /// Returns the athlete's best elapsed time on the segment in seconds
+    pub fn get_pr_time(&self) -> Option<i64> {
+        self.athlete_segment_stats
+            .as_ref()
+            .and_then(|stats| stats.pr_elapsed_time)
+            .or_else(|| self.summary.get_pr_time())
+    }
+}
+
+/// The athlete's personal record on a summary segment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummaryPRSegmentEffort {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_activity_id: Option<ActivityId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_elapsed_time: Option<i64>, // time in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort_count: Option<i64>,
+}
+
+/// The athlete's effort shown on a summary segment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummarySegmentEffort {
+    pub id: SegmentEffortId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_id: Option<ActivityId>,
+    pub elapsed_time: Duration,
+    pub start_date: String,
+    pub start_date_local: String,
+    pub distance: Distance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_kom: Option<bool>,
+}
+
+/// The athlete's stats on a detailed segment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AthleteSegmentStats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_elapsed_time: Option<i64>, // time in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_activity_id: Option<ActivityId>,
+    pub effort_count: i64,
+}
+
+/// Fastest times on the segment, formatted by the API as displayed on Strava e.g. "5:12"
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Xoms {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kom: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qom: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overall: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<XomDestination>,
+}
+
+/// Link to the leaderboard of the segment
+#[derive(Serialize, Deserialize, Debug)]
+pub struct XomDestination {
+    pub href: String,
+    #[serde(rename = "type")]
+    pub destination_type: String,
+    pub name: String,
+}
+
+/// Athlete with the most efforts on the segment over the last 90 days
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LocalLegend {
+    pub athlete_id: AthleteId,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort_description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort_count: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+}
+
+/// Collection of segment efforts
+pub type SegmentEffortCollection = Vec<DetailedSegmentEffort>;
+
+/// Detailed segment effort fields returned from the API
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DetailedSegmentEffort {
+    pub id: SegmentEffortId,
+    pub resource_state: i64,
+    pub name: String,
+    pub activity: MetaActivity,
+    pub athlete: MetaAthlete,
+    pub elapsed_time: Duration,
+    pub moving_time: Duration,
+    pub start_date: String,
+    pub start_date_local: String,
+    pub distance: Distance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_index: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_index: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_cadence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_watts: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_watts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_heartrate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_heartrate: Option<f64>,
+    pub segment: SummarySegment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kom_rank: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_rank: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_kom: Option<bool>,
+}
+
+impl DetailedSegmentEffort {
+    /// Returns the average speed of the effort over its elapsed time
+    pub fn average_speed(&self) -> Speed {
+        if self.elapsed_time.seconds() == 0.0 {
+            return Speed(0.0);
+        }
+        Speed(self.distance.meters() / self.elapsed_time.seconds())
+    }
+
+    /// Returns the UTC start date
+    #[cfg(feature = "chrono")]
+    pub fn start_date_utc(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::models::datetime::parse_utc(&self.start_date)
+    }
+
+    /// Returns the start date on the wall clock where the effort took place
+    #[cfg(feature = "chrono")]
+    pub fn start_date_local_naive(&self) -> Result<chrono::NaiveDateTime, chrono::ParseError> {
+        crate::models::datetime::parse_local(&self.start_date_local)
+    }
+}
+
+/// Segments returned by the segment explorer
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExplorerResponse {
+    pub segments: Vec<ExplorerSegment>,
+}
+
+/// Segment fields returned by the segment explorer
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExplorerSegment {
+    pub id: SegmentId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    pub name: String,
+    pub climb_category: i64,
+    pub climb_category_desc: String,
+    pub avg_grade: f64,
+    pub start_latlng: LatLng,
+    pub end_latlng: LatLng,
+    pub elev_difference: Elevation,
+    pub distance: Distance,
+    pub points: String, // encoded polyline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starred: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_legend_enabled: Option<bool>,
+}
+
+impl ExplorerSegment {
+    /// Decodes the encoded polyline of the segment into coordinates
+    pub fn decode_points(&self) -> Result<Vec<LatLng>, Box<dyn Error>> {
+        polyline::decode(&self.points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::units::UnitSystem;
+
+    #[test]
+    fn detailed_segment_deserializes_with_xoms_and_stats() {
+        let sample = r#"{
+            "id":229781,
+            "resource_state":3,
+            "name":"Hawk Hill",
+            "activity_type":"Ride",
+            "distance":2684.82,
+            "average_grade":5.7,
+            "maximum_grade":14.2,
+            "elevation_high":245.3,
+            "elevation_low":92.4,
+            "start_latlng":[37.8331119,-122.4834356],
+            "end_latlng":[37.8280722,-122.4981393],
+            "climb_category":1,
+            "city":"San Francisco",
+            "state":"CA",
+            "country":"United States",
+            "private":false,
+            "hazardous":false,
+            "starred":true,
+            "created_at":"2009-09-21T20:29:41Z",
+            "updated_at":"2018-02-15T09:04:18Z",
+            "total_elevation_gain":155.733,
+            "map":{"id":"s229781","polyline":"}g|eFnpqjVl@En@Md@HbAd@d@^h@Xx@VbARjBDh@OPQf@w@d@k@XKXDFPH\\EbGT`AV`@v@|@NTNb@?XOb@cAxAWLuE@eAFMBoAv@eBt@q@b@}@tAiAb@}@tAiAb@}@tAiA","resource_state":3},
+            "effort_count":309974,
+            "athlete_count":30623,
+            "star_count":2428,
+            "athlete_segment_stats":{"pr_elapsed_time":553,"pr_date":"1993-04-03","effort_count":2},
+            "xoms":{"kom":"4:48","qom":"5:57","overall":"4:48","destination":{"href":"strava://segments/229781/leaderboard","type":"overall","name":"All-Time"}}
+        }"#;
+        let segment: DetailedSegment = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(segment.activity_type, ActivityType::Ride);
+        assert_eq!(segment.get_pr_time(), Some(553));
+        assert_eq!(segment.xoms.unwrap().kom, Some("4:48".to_string()));
+        assert!(segment.map.polyline.is_some());
+    }
+
+    #[test]
+    fn segment_effort_measures_use_unit_types() {
+        let sample = r#"{
+            "id":1234556789,
+            "resource_state":3,
+            "name":"Alpe d'Huez",
+            "activity":{"id":3454504,"resource_state":1},
+            "athlete":{"id":54321,"resource_state":1},
+            "elapsed_time":381,
+            "moving_time":340,
+            "start_date":"2018-02-12T16:12:41Z",
+            "start_date_local":"2018-02-12T08:12:41Z",
+            "distance":1905.0,
+            "segment":{"id":673683,"activity_type":"Ride","distance":1905.0}
+        }"#;
+        let effort: DetailedSegmentEffort = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(effort.elapsed_time.format(), "6:21");
+        assert_eq!(effort.distance.format(UnitSystem::Metric), "1.9 km");
+        assert_eq!(
+            effort.average_speed().format(UnitSystem::Metric),
+            "18.0 km/h"
+        );
+        assert_eq!(effort.segment.distance, effort.distance);
+    }
+
+    #[test]
+    fn detailed_segment_round_trips() {
+        let sample = r#"{
+            "id":229781,
+            "resource_state":3,
+            "name":"Hawk Hill",
+            "activity_type":"Ride",
+            "distance":2684.82,
+            "average_grade":5.7,
+            "maximum_grade":14.2,
+            "map":{"id":"s229781","polyline":"_p~iF~ps|U_ulLnnqC_mqNvxq`@","resource_state":3},
+            "effort_count":309974,
+            "athlete_segment_stats":{"pr_elapsed_time":553,"pr_date":"1993-04-03","effort_count":2}
+        }"#;
+        let segment: DetailedSegment = serde_json::from_str(sample).unwrap();
+        let json = serde_json::to_string(&segment).unwrap();
+        assert_eq!(json.matches("\"athlete_segment_stats\"").count(), 1);
+
+        let again: DetailedSegment = serde_json::from_str(&json).unwrap();
+        assert_eq!(again.get_pr_time(), Some(553));
+        assert_eq!(serde_json::to_string(&again).unwrap(), json);
+    }
+
+    #[test]
+    fn explorer_segment_decodes_points() {
+        let sample = r#"{"segments":[{
+            "id":229781,
+            "resource_state":2,
+            "name":"Hawk Hill",
+            "climb_category":1,
+            "climb_category_desc":"4",
+            "avg_grade":5.7,
+            "start_latlng":[38.5,-120.2],
+            "end_latlng":[43.252,-126.453],
+            "elev_difference":152.8,
+            "distance":2684.8,
+            "points":"_p~iF~ps|U_ulLnnqC_mqNvxq`@",
+            "starred":false
+        }]}"#;
+        let response: ExplorerResponse = serde_json::from_str(sample).unwrap();
+        let segment = &response.segments[0];
+        let points = segment.decode_points().unwrap();
+
+        assert_eq!(segment.start_latlng, LatLng::new(38.5, -120.2));
+        assert_eq!(
+            points,
+            vec![
+                LatLng::new(38.5, -120.2),
+                LatLng::new(40.7, -120.95),
+                LatLng::new(43.252, -126.453)
+            ]
+        );
+    }
+}
@@ -0,0 +1,320 @@
+//! # Streams model
+//! This model is used to represent the streams returned by the Strava API when `key_by_type` is set.
+//! Documentation: https://developers.strava.com/docs/reference/#api-models-StreamSet
+//! Every stream of a set is aligned by index, so sample `i` of each stream was recorded at the same point
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Keys of the streams that can be requested from the API
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamType {
+    Time,
+    Distance,
+    Latlng,
+    Altitude,
+    VelocitySmooth,
+    Heartrate,
+    Cadence,
+    Watts,
+    Temp,
+    Moving,
+    GradeSmooth,
+}
+
+impl StreamType {
+    /// Every stream type, used to request all the streams at once
+    pub const ALL: [StreamType; 11] = [
+        StreamType::Time,
+        StreamType::Distance,
+        StreamType::Latlng,
+        StreamType::Altitude,
+        StreamType::VelocitySmooth,
+        StreamType::Heartrate,
+        StreamType::Cadence,
+        StreamType::Watts,
+        StreamType::Temp,
+        StreamType::Moving,
+        StreamType::GradeSmooth,
+    ];
+
+    /// The only stream types the API returns for a segment
+    pub const SEGMENT: [StreamType; 3] = [
+        StreamType::Distance,
+        StreamType::Latlng,
+        StreamType::Altitude,
+    ];
+
+    /// Returns the key used by the API for the stream type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamType::Time => "time",
+            StreamType::Distance => "distance",
+            StreamType::Latlng => "latlng",
+            StreamType::Altitude => "altitude",
+            StreamType::VelocitySmooth => "velocity_smooth",
+            StreamType::Heartrate => "heartrate",
+            StreamType::Cadence => "cadence",
+            StreamType::Watts => "watts",
+            StreamType::Temp => "temp",
+            StreamType::Moving => "moving",
+            StreamType::GradeSmooth => "grade_smooth",
+        }
+    }
+}
+
+impl fmt::Display for StreamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Sampling resolution of the streams, low is about 100 points, medium 1000 and high 10000
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    Low,
+    Medium,
+    High,
+}
+
+impl Resolution {
+    /// Returns the value used by the API for the resolution
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resolution::Low => "low",
+            Resolution::Medium => "medium",
+            Resolution::High => "high",
+        }
+    }
+}
+
+/// A single stream, `data` holds one value per sample
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Stream<T> {
+    pub data: Vec<T>,
+    pub series_type: Option<String>,
+    pub original_size: Option<i64>,
+    pub resolution: Option<String>,
+}
+
+/// Set of streams keyed by type, streams that were not requested or not recorded are `None`
+/// and left out when serialized, like the API does
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct StreamSet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<Stream<i64>>, // seconds since start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<Stream<f64>>, // distance in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latlng: Option<Stream<[f64; 2]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<Stream<f64>>, // altitude in meters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub velocity_smooth: Option<Stream<f64>>, // speed in meters per second
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heartrate: Option<Stream<i64>>, // beats per minute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cadence: Option<Stream<i64>>, // rotations per minute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watts: Option<Stream<Option<i64>>>, // power in watts, null while coasting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp: Option<Stream<i64>>, // degrees celsius
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moving: Option<Stream<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grade_smooth: Option<Stream<f64>>, // grade in percent
+}
+
+/// A stream returned as an element of a list, as the routes endpoint does not key streams by type
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TypedStream {
+    /// None for a stream type this crate does not know, so it does not fail the whole list
+    #[serde(rename = "type", deserialize_with = "known_stream_type")]
+    pub stream_type: Option<StreamType>,
+    #[serde(flatten)]
+    pub stream: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Deserializes a stream type, mapping the types missing from `StreamType` to None
+fn known_stream_type<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<StreamType>, D::Error> {
+    let key = String::deserialize(deserializer)?;
+    Ok(StreamType::ALL
+        .into_iter()
+        .find(|stream_type| stream_type.as_str() == key))
+}
+
+/// Values of every stream at one sample index
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamPoint {
+    pub time: Option<i64>,
+    pub distance: Option<f64>,
+    pub latlng: Option<[f64; 2]>,
+    pub altitude: Option<f64>,
+    pub velocity_smooth: Option<f64>,
+    pub heartrate: Option<i64>,
+    pub cadence: Option<i64>,
+    pub watts: Option<i64>,
+    pub temp: Option<i64>,
+    pub moving: Option<bool>,
+    pub grade_smooth: Option<f64>,
+}
+
+/// Returns the sample at index from an optional stream
+fn sample<T: Copy>(stream: &Option<Stream<T>>, index: usize) -> Option<T> {
+    stream
+        .as_ref()
+        .and_then(|stream| stream.data.get(index).copied())
+}
+
+impl StreamSet {
+    /// Build a set from streams returned as a list, keying each stream by its type.
+    /// Streams of a type this crate does not know are skipped
+    pub fn from_list(streams: Vec<TypedStream>) -> Result<StreamSet, serde_json::Error> {
+        let keyed: serde_json::Map<String, serde_json::Value> = streams
+            .into_iter()
+            .filter_map(|typed| {
+                let stream_type = typed.stream_type?;
+                Some((
+                    stream_type.as_str().to_string(),
+                    serde_json::Value::Object(typed.stream),
+                ))
+            })
+            .collect();
+        serde_json::from_value(serde_json::Value::Object(keyed))
+    }
+
+    /// Returns the number of samples, the length of the longest stream in the set
+    pub fn len(&self) -> usize {
+        [
+            self.time.as_ref().map(|s| s.data.len()),
+            self.distance.as_ref().map(|s| s.data.len()),
+            self.latlng.as_ref().map(|s| s.data.len()),
+            self.altitude.as_ref().map(|s| s.data.len()),
+            self.velocity_smooth.as_ref().map(|s| s.data.len()),
+            self.heartrate.as_ref().map(|s| s.data.len()),
+            self.cadence.as_ref().map(|s| s.data.len()),
+            self.watts.as_ref().map(|s| s.data.len()),
+            self.temp.as_ref().map(|s| s.data.len()),
+            self.moving.as_ref().map(|s| s.data.len()),
+            self.grade_smooth.as_ref().map(|s| s.data.len()),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0)
+    }
+
+    /// Returns true when the set holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the stream types present in the set
+    pub fn stream_types(&self) -> Vec<StreamType> {
+        StreamType::ALL
+            .into_iter()
+            .filter(|stream_type| self.has(*stream_type))
+            .collect()
+    }
+
+    /// Returns true when the stream type is present in the set
+    pub fn has(&self, stream_type: StreamType) -> bool {
+        match stream_type {
+            StreamType::Time => self.time.is_some(),
+            StreamType::Distance => self.distance.is_some(),
+            StreamType::Latlng => self.latlng.is_some(),
+            StreamType::Altitude => self.altitude.is_some(),
+            StreamType::VelocitySmooth => self.velocity_smooth.is_some(),
+            StreamType::Heartrate => self.heartrate.is_some(),
+            StreamType::Cadence => self.cadence.is_some(),
+            StreamType::Watts => self.watts.is_some(),
+            StreamType::Temp => self.temp.is_some(),
+            StreamType::Moving => self.moving.is_some(),
+            StreamType::GradeSmooth => self.grade_smooth.is_some(),
+        }
+    }
+
+    /// Returns the values of every stream at the sample index, or `None` past the end of the set
+    pub fn point(&self, index: usize) -> Option<StreamPoint> {
+        if index >= self.len() {
+            return None;
+        }
+        Some(StreamPoint {
+            time: sample(&self.time, index),
+            distance: sample(&self.distance, index),
+            latlng: sample(&self.latlng, index),
+            altitude: sample(&self.altitude, index),
+            velocity_smooth: sample(&self.velocity_smooth, index),
+            heartrate: sample(&self.heartrate, index),
+            cadence: sample(&self.cadence, index),
+            watts: sample(&self.watts, index).flatten(),
+            temp: sample(&self.temp, index),
+            moving: sample(&self.moving, index),
+            grade_smooth: sample(&self.grade_smooth, index),
+        })
+    }
+
+    /// Iterate over every sample point of the set in order
+    pub fn points(&self) -> impl Iterator<Item = StreamPoint> + '_ {
+        (0..self.len()).filter_map(move |index| self.point(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_set_iterates_aligned_points() {
+        let sample = r#"{
+            "time":{"data":[0,1,2],"series_type":"distance","original_size":3,"resolution":"high"},
+            "latlng":{"data":[[37.7,-122.4],[37.71,-122.41],[37.72,-122.42]],"series_type":"distance","original_size":3,"resolution":"high"},
+            "watts":{"data":[210,null,180],"series_type":"distance","original_size":3,"resolution":"high"}
+        }"#;
+        let streams: StreamSet = serde_json::from_str(sample).unwrap();
+
+        assert_eq!(streams.len(), 3);
+        assert_eq!(
+            streams.stream_types(),
+            vec![StreamType::Time, StreamType::Latlng, StreamType::Watts]
+        );
+        let points: Vec<StreamPoint> = streams.points().collect();
+        assert_eq!(points[1].time, Some(1));
+        assert_eq!(points[1].latlng, Some([37.71, -122.41]));
+        assert_eq!(points[1].watts, None);
+        assert_eq!(points[2].watts, Some(180));
+        assert_eq!(points[2].heartrate, None);
+        assert!(streams.point(3).is_none());
+    }
+
+    #[test]
+    fn stream_set_from_list_keys_streams_by_type() {
+        let sample = r#"[
+            {"type":"distance","data":[0.0,12.5],"series_type":"distance","original_size":2,"resolution":"high"},
+            {"type":"altitude","data":[10.2,11.0],"series_type":"distance","original_size":2,"resolution":"high"}
+        ]"#;
+        let list: Vec<TypedStream> = serde_json::from_str(sample).unwrap();
+        let streams = StreamSet::from_list(list).unwrap();
+
+        assert_eq!(streams.distance.unwrap().data, vec![0.0, 12.5]);
+        assert_eq!(streams.altitude.unwrap().data, vec![10.2, 11.0]);
+        assert!(streams.time.is_none());
+    }
+
+    #[test]
+    fn stream_set_from_list_skips_unknown_types() {
+        let sample = r#"[
+            {"type":"latlng","data":[[37.8,-122.4],[37.81,-122.41]],"series_type":"distance","original_size":2,"resolution":"high"},
+            {"type":"power_zone","data":[1,2],"series_type":"distance","original_size":2,"resolution":"high"}
+        ]"#;
+        let list: Vec<TypedStream> = serde_json::from_str(sample).unwrap();
+        assert_eq!(list[1].stream_type, None);
+        let streams = StreamSet::from_list(list).unwrap();
+
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams.latlng.unwrap().original_size, Some(2));
+    }
+}
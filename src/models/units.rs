@@ -0,0 +1,291 @@
+//! # Units model
+//! Value types for the measures returned by the Strava API, always stored in the API's units:
+//! meters, seconds, meters per second and kilograms.
+//! Contains conversions and human formatting in the metric or imperial system,
+//! e.g. `12.4 mi` or `5:12 /km`
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+const METERS_PER_KILOMETER: f64 = 1000.0;
+const METERS_PER_MILE: f64 = 1609.344;
+const FEET_PER_METER: f64 = 3.28084;
+const POUNDS_PER_KILOGRAM: f64 = 2.20462;
+
+/// System used to format measures, matches the athlete's `measurement_preference`
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnitSystem {
+    #[default]
+    #[serde(rename = "meters")]
+    Metric,
+    #[serde(rename = "feet")]
+    Imperial,
+}
+
+/// Formats seconds as `h:mm:ss`, or `m:ss` under an hour
+fn format_clock(seconds: f64) -> String {
+    let seconds = seconds.round() as i64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// Distance in meters
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[serde(transparent)]
+pub struct Distance(pub f64);
+
+impl Distance {
+    pub fn from_kilometers(kilometers: f64) -> Distance {
+        Distance(kilometers * METERS_PER_KILOMETER)
+    }
+
+    pub fn from_miles(miles: f64) -> Distance {
+        Distance(miles * METERS_PER_MILE)
+    }
+
+    pub fn meters(&self) -> f64 {
+        self.0
+    }
+
+    pub fn kilometers(&self) -> f64 {
+        self.0 / METERS_PER_KILOMETER
+    }
+
+    pub fn miles(&self) -> f64 {
+        self.0 / METERS_PER_MILE
+    }
+
+    /// Formats the distance with one decimal, e.g. `12.4 km` or `7.7 mi`
+    pub fn format(&self, system: UnitSystem) -> String {
+        match system {
+            UnitSystem::Metric => format!("{:.1} km", self.kilometers()),
+            UnitSystem::Imperial => format!("{:.1} mi", self.miles()),
+        }
+    }
+}
+
+impl fmt::Display for Distance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(UnitSystem::Metric))
+    }
+}
+
+/// Duration in seconds, serialized as an integer when it holds whole seconds like the API does
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Duration(pub f64);
+
+impl Duration {
+    pub fn from_minutes(minutes: f64) -> Duration {
+        Duration(minutes * 60.0)
+    }
+
+    pub fn seconds(&self) -> f64 {
+        self.0
+    }
+
+    pub fn minutes(&self) -> f64 {
+        self.0 / 60.0
+    }
+
+    pub fn hours(&self) -> f64 {
+        self.0 / 3600.0
+    }
+
+    /// Formats the duration as a clock, e.g. `1:02:03` or `42:10`
+    pub fn format(&self) -> String {
+        format_clock(self.0)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format())
+    }
+}
+
+impl From<Duration> for std::time::Duration {
+    fn from(duration: Duration) -> std::time::Duration {
+        std::time::Duration::from_secs_f64(duration.0.max(0.0))
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.fract() == 0.0 {
+            serializer.serialize_i64(self.0 as i64)
+        } else {
+            serializer.serialize_f64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        f64::deserialize(deserializer).map(Duration)
+    }
+}
+
+/// Speed in meters per second
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[serde(transparent)]
+pub struct Speed(pub f64);
+
+impl Speed {
+    pub fn meters_per_second(&self) -> f64 {
+        self.0
+    }
+
+    pub fn kilometers_per_hour(&self) -> f64 {
+        self.0 * 3600.0 / METERS_PER_KILOMETER
+    }
+
+    pub fn miles_per_hour(&self) -> f64 {
+        self.0 * 3600.0 / METERS_PER_MILE
+    }
+
+    /// Returns the pace at this speed
+    pub fn pace(&self) -> Pace {
+        Pace(1.0 / self.0)
+    }
+
+    /// Formats the speed with one decimal, e.g. `28.4 km/h` or `17.6 mi/h`
+    pub fn format(&self, system: UnitSystem) -> String {
+        match system {
+            UnitSystem::Metric => format!("{:.1} km/h", self.kilometers_per_hour()),
+            UnitSystem::Imperial => format!("{:.1} mi/h", self.miles_per_hour()),
+        }
+    }
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(UnitSystem::Metric))
+    }
+}
+
+/// Pace in seconds per meter, infinite when standing still
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Pace(pub f64);
+
+impl Pace {
+    /// Returns the pace of covering the distance in the duration
+    pub fn from_distance(distance: Distance, duration: Duration) -> Pace {
+        Pace(duration.seconds() / distance.meters())
+    }
+
+    pub fn seconds_per_kilometer(&self) -> f64 {
+        self.0 * METERS_PER_KILOMETER
+    }
+
+    pub fn seconds_per_mile(&self) -> f64 {
+        self.0 * METERS_PER_MILE
+    }
+
+    /// Formats the pace as minutes per km or mile, e.g. `5:12 /km` or `8:22 /mi`
+    pub fn format(&self, system: UnitSystem) -> String {
+        let (seconds, unit) = match system {
+            UnitSystem::Metric => (self.seconds_per_kilometer(), "/km"),
+            UnitSystem::Imperial => (self.seconds_per_mile(), "/mi"),
+        };
+        if seconds.is_finite() {
+            format!("{} {}", format_clock(seconds), unit)
+        } else {
+            format!("-:-- {}", unit)
+        }
+    }
+}
+
+impl fmt::Display for Pace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(UnitSystem::Metric))
+    }
+}
+
+/// Elevation or elevation gain in meters
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[serde(transparent)]
+pub struct Elevation(pub f64);
+
+impl Elevation {
+    pub fn meters(&self) -> f64 {
+        self.0
+    }
+
+    pub fn feet(&self) -> f64 {
+        self.0 * FEET_PER_METER
+    }
+
+    /// Formats the elevation rounded to the unit, e.g. `152 m` or `499 ft`
+    pub fn format(&self, system: UnitSystem) -> String {
+        match system {
+            UnitSystem::Metric => format!("{:.0} m", self.meters()),
+            UnitSystem::Imperial => format!("{:.0} ft", self.feet()),
+        }
+    }
+}
+
+impl fmt::Display for Elevation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(UnitSystem::Metric))
+    }
+}
+
+/// Weight in kilograms
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[serde(transparent)]
+pub struct Weight(pub f64);
+
+impl Weight {
+    pub fn from_pounds(pounds: f64) -> Weight {
+        Weight(pounds / POUNDS_PER_KILOGRAM)
+    }
+
+    pub fn kilograms(&self) -> f64 {
+        self.0
+    }
+
+    pub fn pounds(&self) -> f64 {
+        self.0 * POUNDS_PER_KILOGRAM
+    }
+
+    /// Formats the weight with one decimal, e.g. `70.0 kg` or `154.3 lb`
+    pub fn format(&self, system: UnitSystem) -> String {
+        match system {
+            UnitSystem::Metric => format!("{:.1} kg", self.kilograms()),
+            UnitSystem::Imperial => format!("{:.1} lb", self.pounds()),
+        }
+    }
+}
+
+impl fmt::Display for Weight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(UnitSystem::Metric))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_convert_and_format() {
+        let walk = Distance(1448.4);
+        assert!((walk.miles() - 0.9).abs() < 0.001);
+        assert_eq!(walk.format(UnitSystem::Imperial), "0.9 mi");
+        assert_eq!(Distance(19956.0).format(UnitSystem::Imperial), "12.4 mi");
+
+        let run = Pace::from_distance(Distance(10_000.0), Duration(3120.0));
+        assert_eq!(run.format(UnitSystem::Metric), "5:12 /km");
+        assert_eq!(run.format(UnitSystem::Imperial), "8:22 /mi");
+        assert_eq!(Speed(0.0).pace().format(UnitSystem::Metric), "-:-- /km");
+
+        assert_eq!(Duration(3723.0).format(), "1:02:03");
+        assert_eq!(Duration(90.0).minutes(), 1.5);
+        assert_eq!(Elevation(152.0).format(UnitSystem::Imperial), "499 ft");
+        assert_eq!(Weight(70.0).format(UnitSystem::Imperial), "154.3 lb");
+        assert_eq!(serde_json::to_string(&Duration(660.0)).unwrap(), "660");
+    }
+}
@@ -0,0 +1,85 @@
+//! # Uploads model
+//! This model is used to represent the upload object returned by the Strava API
+//! Documentation: https://developers.strava.com/docs/reference/#api-models-Upload
+//! Contains helper methods to check the processing state of an upload
+use crate::models::ids::{ActivityId, UploadId};
+use serde::{Deserialize, Serialize};
+
+/// Upload fields returned from the API
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Upload {
+    pub id: UploadId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_str: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activity_id: Option<ActivityId>,
+}
+
+impl Upload {
+    /// Returns true once Strava created the activity from the file
+    pub fn is_ready(&self) -> bool {
+        self.activity_id.is_some()
+    }
+
+    /// Returns true when Strava failed to process the file, including duplicates
+    pub fn is_error(&self) -> bool {
+        self.error.as_ref().is_some_and(|error| !error.is_empty())
+    }
+
+    /// Returns true when the file was already uploaded as another activity
+    pub fn is_duplicate(&self) -> bool {
+        self.error
+            .as_ref()
+            .is_some_and(|error| error.contains("duplicate of"))
+    }
+
+    /// Returns true while Strava is still processing the file
+    pub fn is_processing(&self) -> bool {
+        !self.is_ready() && !self.is_error()
+    }
+
+    /// Returns the id of the existing activity a duplicate upload refers to
+    ///
+    /// The API reports it in the error message, e.g. `file.fit duplicate of activity 12345`.
+    pub fn duplicate_activity_id(&self) -> Option<ActivityId> {
+        let error = self.error.as_ref().filter(|_| self.is_duplicate())?;
+        let (_, activity) = error.split_once("duplicate of")?;
+        activity
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())?
+            .parse()
+            .ok()
+            .map(ActivityId)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upload_states_and_duplicate_activity_id() {
+        let processing: Upload = serde_json::from_str(
+            r#"{"id":16486788,"id_str":"16486788","external_id":"ride.fit","error":null,"status":"Your activity is still being processed.","activity_id":null}"#,
+        )
+        .unwrap();
+        let duplicate: Upload = serde_json::from_str(
+            r#"{"id":16486789,"id_str":"16486789","external_id":"ride.fit","error":"ride.fit duplicate of <a href='/activities/13997614562' target='_blank'>Morning Ride</a>","status":"There was an error processing your activity.","activity_id":null}"#,
+        )
+        .unwrap();
+
+        assert!(processing.is_processing());
+        assert!(duplicate.is_error());
+        assert!(duplicate.is_duplicate());
+        assert_eq!(
+            duplicate.duplicate_activity_id(),
+            Some(ActivityId(13997614562))
+        );
+        assert_eq!(processing.duplicate_activity_id(), None);
+    }
+}
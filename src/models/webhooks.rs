@@ -0,0 +1,139 @@
+//! # Webhooks model
+//! This model is used to represent the push subscription of the application and the events
+//! Strava pushes to it.
+//! Contains helper methods to identify activity and deauthorization events
+//! Documentation: https://developers.strava.com/docs/webhooks/
+use crate::models::ids::{ActivityId, AthleteId, SubscriptionId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Push subscription fields returned from the API
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Subscription {
+    pub id: SubscriptionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_state: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+/// Type of the object an event refers to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectType {
+    Activity,
+    Athlete,
+}
+
+/// Change that triggered an event
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AspectType {
+    Create,
+    Update,
+    Delete,
+}
+
+/// Event pushed by Strava to the subscription callback
+/// Documentation: https://developers.strava.com/docs/webhooks/#event-data
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookEvent {
+    pub object_type: ObjectType,
+    pub object_id: i64, // id of an activity or an athlete depending on object_type
+    pub aspect_type: AspectType,
+    #[serde(default)]
+    pub updates: HashMap<String, serde_json::Value>, // changed fields, e.g. title, type, private
+    pub owner_id: AthleteId,
+    pub subscription_id: SubscriptionId,
+    pub event_time: i64, // epoch seconds
+}
+
+impl WebhookEvent {
+    /// Returns true when the event is about an activity
+    pub fn is_activity(&self) -> bool {
+        self.object_type == ObjectType::Activity
+    }
+
+    /// Returns the id of the activity the event is about, None for athlete events
+    pub fn activity_id(&self) -> Option<ActivityId> {
+        (self.object_type == ObjectType::Activity).then_some(ActivityId(self.object_id))
+    }
+
+    /// Returns the id of the athlete the event is about, None for activity events
+    pub fn athlete_id(&self) -> Option<AthleteId> {
+        (self.object_type == ObjectType::Athlete).then_some(AthleteId(self.object_id))
+    }
+
+    /// Returns true when the athlete revoked the access of the application
+    pub fn is_deauthorization(&self) -> bool {
+        self.object_type == ObjectType::Athlete
+            && self.aspect_type == AspectType::Update
+            && self.is_update_to("authorized", "false")
+    }
+
+    /// Returns true when the event sets the field to the value. Strava sends updated values as
+    /// strings, e.g. `"private":"true"`, booleans and numbers are compared by value
+    pub fn is_update_to(&self, field: &str, value: &str) -> bool {
+        match self.updates.get(field) {
+            Some(serde_json::Value::String(updated)) => updated == value,
+            Some(updated @ (serde_json::Value::Bool(_) | serde_json::Value::Number(_))) => {
+                serde_json::from_str::<serde_json::Value>(value)
+                    .is_ok_and(|value| value == *updated)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_event_deserializes_updates() {
+        let sample = r#"{
+            "aspect_type":"update",
+            "event_time":1516126040,
+            "object_id":1360128428,
+            "object_type":"activity",
+            "owner_id":134815,
+            "subscription_id":120475,
+            "updates":{"title":"Messy","private":"true"}
+        }"#;
+        let event: WebhookEvent = serde_json::from_str(sample).unwrap();
+
+        assert!(event.is_activity());
+        assert_eq!(event.aspect_type, AspectType::Update);
+        assert_eq!(event.updates.get("title"), Some(&"Messy".into()));
+        assert!(event.is_update_to("private", "true"));
+        assert!(!event.is_deauthorization());
+        assert_eq!(event.activity_id(), Some(ActivityId(1360128428)));
+        assert_eq!(event.athlete_id(), None);
+    }
+
+    #[test]
+    fn webhook_event_accepts_scalar_updates() {
+        let sample = r#"{
+            "aspect_type":"update",
+            "event_time":1516126040,
+            "object_id":134815,
+            "object_type":"athlete",
+            "owner_id":134815,
+            "subscription_id":120475,
+            "updates":{"authorized":false,"weight":61.5}
+        }"#;
+        let event: WebhookEvent = serde_json::from_str(sample).unwrap();
+
+        assert!(event.is_deauthorization());
+        assert_eq!(event.athlete_id(), Some(AthleteId(134815)));
+        assert_eq!(event.activity_id(), None);
+        assert!(event.is_update_to("weight", "61.5"));
+        assert!(!event.is_update_to("title", "false"));
+    }
+}
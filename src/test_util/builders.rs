@@ -0,0 +1,593 @@
+//! # Builders
+//! Builders of the models with realistic defaults, so a test only sets the fields it checks.
+//! Each builder holds the JSON object Strava would return and deserializes it on `build`,
+//! so the models go through the same serde code as responses from the API.
+//! `to_json` returns the object to serve from a mock server.
+
+use crate::models::activities::{DetailedActivity, SportType, SummaryActivity, Visibility};
+use crate::models::athlete::{
+    DetailedAthlete, MeasurementPreference, MetaAthlete, Sex, SummaryAthlete,
+};
+use crate::models::clubs::{ClubSportType, DetailedClub, Membership, SummaryClub};
+use crate::models::geo::LatLng;
+use crate::models::ids::{ActivityId, AthleteId, ClubId, GearId};
+use crate::models::units::{Distance, Duration, Elevation, Speed, Weight};
+use crate::util::polyline;
+use crate::util::timestamp::format_utc_timestamp;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+/// Sets a field of the JSON object, a `None` value removes the field like the models leave it out
+fn set(fields: &mut Map<String, Value>, key: &str, value: impl Serialize) {
+    match serde_json::to_value(value).expect("Fixture value must serialize to JSON") {
+        Value::Null => fields.remove(key),
+        value => fields.insert(key.to_string(), value),
+    };
+}
+
+/// Deserializes the JSON object into a model, panics with the offending JSON on failure
+fn build<T: DeserializeOwned>(fields: &Map<String, Value>) -> T {
+    serde_json::from_value(Value::Object(fields.clone())).unwrap_or_else(|error| {
+        panic!(
+            "Fixture does not deserialize: {}\n{:#}",
+            error,
+            Value::Object(fields.clone())
+        )
+    })
+}
+
+/// Returns the fields of a `json!` object
+fn object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        _ => unreachable!("Fixture defaults are JSON objects"),
+    }
+}
+
+/// Builder of activities, defaults to a 10 km morning run in San Francisco.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::models::activities::SportType;
+/// use strava_client_rs::models::units::Distance;
+/// use strava_client_rs::test_util::ActivityBuilder;
+///
+/// let ride = ActivityBuilder::new()
+///     .sport_type(SportType::GravelRide)
+///     .distance(Distance::from_kilometers(42.0))
+///     .build();
+/// assert!(ride.sport_type.is_ride());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityBuilder {
+    fields: Map<String, Value>,
+}
+
+impl Default for ActivityBuilder {
+    fn default() -> ActivityBuilder {
+        ActivityBuilder::new()
+    }
+}
+
+impl ActivityBuilder {
+    pub fn new() -> ActivityBuilder {
+        let start = LatLng::new(37.77, -122.45);
+        let end = LatLng::new(37.80, -122.47);
+        let mut builder = ActivityBuilder {
+            fields: object(json!({
+                "resource_state": 2,
+                "athlete": {"id": 134815, "resource_state": 1},
+                "name": "Morning Run",
+                "distance": 10000.0,
+                "moving_time": 3000,
+                "elapsed_time": 3120,
+                "total_elevation_gain": 85.0,
+                "type": "Run",
+                "sport_type": "Run",
+                "id": 12345678987654321i64,
+                "location_country": "United States",
+                "achievement_count": 2,
+                "kudos_count": 12,
+                "comment_count": 1,
+                "athlete_count": 1,
+                "photo_count": 0
+            })),
+        };
+        builder.fields.extend(object(json!({
+            "map": {
+                "id": "a12345678987654321",
+                "summary_polyline": polyline::encode(&[start, end]),
+                "resource_state": 2
+            },
+            "trainer": false,
+            "commute": false,
+            "manual": false,
+            "private": false,
+            "visibility": "everyone",
+            "flagged": false,
+            "gear_id": "g12345",
+            "start_latlng": [start.lat, start.lng],
+            "end_latlng": [end.lat, end.lng],
+            "average_speed": 3.333,
+            "max_speed": 4.8,
+            "average_cadence": 84.5,
+            "has_heartrate": true,
+            "average_heartrate": 148.2,
+            "max_heartrate": 171.0,
+            "heartrate_opt_out": false,
+            "display_hide_heartrate_option": true,
+            "elev_high": 62.4,
+            "elev_low": 3.1,
+            "upload_id": 987654321234567i64,
+            "upload_id_str": "987654321234567",
+            "external_id": "garmin_push_12345678987654321",
+            "from_accepted_tag": false,
+            "pr_count": 1,
+            "total_photo_count": 0,
+            "has_kudoed": false
+        })));
+        builder
+            .timezone("(GMT-08:00) America/Los_Angeles", -28800)
+            .start_date(1704898800)
+    }
+
+    /// Sets any field by its API name, for fields without a dedicated setter
+    pub fn field(mut self, key: &str, value: impl Serialize) -> ActivityBuilder {
+        set(&mut self.fields, key, value);
+        self
+    }
+
+    pub fn id(self, id: ActivityId) -> ActivityBuilder {
+        let map_id = format!("a{}", id);
+        self.field("id", id).map_id(&map_id)
+    }
+
+    fn map_id(mut self, map_id: &str) -> ActivityBuilder {
+        if let Some(Value::Object(map)) = self.fields.get_mut("map") {
+            map.insert("id".to_string(), json!(map_id));
+        }
+        self
+    }
+
+    pub fn athlete_id(self, athlete_id: AthleteId) -> ActivityBuilder {
+        self.field("athlete", json!({"id": athlete_id, "resource_state": 1}))
+    }
+
+    pub fn name(self, name: &str) -> ActivityBuilder {
+        self.field("name", name)
+    }
+
+    /// Sets the sport type, and the legacy type to the same value when it exists
+    pub fn sport_type(self, sport_type: SportType) -> ActivityBuilder {
+        let legacy = match crate::models::activities::ActivityType::from(sport_type.as_str()) {
+            crate::models::activities::ActivityType::Unknown(_) => "Workout".to_string(),
+            known => known.to_string(),
+        };
+        self.field("sport_type", sport_type).field("type", legacy)
+    }
+
+    /// Sets the distance and updates the average speed
+    pub fn distance(self, distance: Distance) -> ActivityBuilder {
+        self.field("distance", distance).update_average_speed()
+    }
+
+    /// Sets the moving time, updates the average speed and keeps the elapsed time above it
+    pub fn moving_time(self, moving_time: Duration) -> ActivityBuilder {
+        let elapsed = self.get_f64("elapsed_time").max(moving_time.seconds());
+        self.field("moving_time", moving_time)
+            .field("elapsed_time", Duration(elapsed))
+            .update_average_speed()
+    }
+
+    pub fn elapsed_time(self, elapsed_time: Duration) -> ActivityBuilder {
+        self.field("elapsed_time", elapsed_time)
+    }
+
+    pub fn total_elevation_gain(self, elevation_gain: Elevation) -> ActivityBuilder {
+        self.field("total_elevation_gain", elevation_gain)
+    }
+
+    pub fn max_speed(self, max_speed: Speed) -> ActivityBuilder {
+        self.field("max_speed", max_speed)
+    }
+
+    /// Sets the start date from epoch seconds, the local date follows the UTC offset
+    pub fn start_date(self, epoch_seconds: i64) -> ActivityBuilder {
+        let offset = self.get_f64("utc_offset") as i64;
+        self.field("start_date", format_utc_timestamp(epoch_seconds))
+            .field(
+                "start_date_local",
+                format_utc_timestamp(epoch_seconds + offset),
+            )
+    }
+
+    /// Sets the timezone label, e.g. `(GMT+01:00) Europe/Paris`, and its UTC offset in seconds.
+    /// Call before `start_date`
+    pub fn timezone(self, label: &str, utc_offset: i64) -> ActivityBuilder {
+        self.field("timezone", label)
+            .field("utc_offset", utc_offset as f64)
+    }
+
+    /// Sets the start and end points and a straight summary polyline between them
+    pub fn route(mut self, start: LatLng, end: LatLng) -> ActivityBuilder {
+        if let Some(Value::Object(map)) = self.fields.get_mut("map") {
+            map.insert(
+                "summary_polyline".to_string(),
+                json!(polyline::encode(&[start, end])),
+            );
+        }
+        self.field("start_latlng", start).field("end_latlng", end)
+    }
+
+    pub fn gear_id(self, gear_id: Option<GearId>) -> ActivityBuilder {
+        self.field("gear_id", gear_id)
+    }
+
+    pub fn commute(self, commute: bool) -> ActivityBuilder {
+        self.field("commute", commute)
+    }
+
+    pub fn trainer(self, trainer: bool) -> ActivityBuilder {
+        self.field("trainer", trainer)
+    }
+
+    /// Sets the visibility, and flags the activity private when only the athlete can see it
+    pub fn visibility(self, visibility: Visibility) -> ActivityBuilder {
+        let private = visibility == Visibility::OnlyMe;
+        self.field("visibility", visibility)
+            .field("private", private)
+    }
+
+    /// Sets the average and max heart rate, None for an activity without heart rate
+    pub fn heartrate(self, heartrate: Option<(f64, f64)>) -> ActivityBuilder {
+        let (average, max) = heartrate.unzip();
+        self.field("has_heartrate", heartrate.is_some())
+            .field("average_heartrate", average)
+            .field("max_heartrate", max)
+    }
+
+    /// Sets the average power measured by a power meter, None for an activity without power
+    pub fn watts(self, average_watts: Option<f64>) -> ActivityBuilder {
+        let kilojoules = average_watts.map(|watts| watts * self.get_f64("moving_time") / 1000.0);
+        self.field("average_watts", average_watts)
+            .field(
+                "weighted_average_watts",
+                average_watts.map(|w| (w * 1.05) as i64),
+            )
+            .field("kilojoules", kilojoules)
+            .field("device_watts", average_watts.is_some())
+    }
+
+    fn get_f64(&self, key: &str) -> f64 {
+        self.fields.get(key).and_then(Value::as_f64).unwrap_or(0.0)
+    }
+
+    fn update_average_speed(self) -> ActivityBuilder {
+        let moving_time = self.get_f64("moving_time");
+        let speed = if moving_time > 0.0 {
+            self.get_f64("distance") / moving_time
+        } else {
+            0.0
+        };
+        self.field("average_speed", Speed(speed))
+    }
+
+    /// Returns the activity as Strava's JSON
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.fields.clone())
+    }
+
+    pub fn build(&self) -> SummaryActivity {
+        build(&self.fields)
+    }
+
+    /// Builds the activity as returned by `get_activities_by_id`, with detailed fields
+    pub fn build_detailed(&self) -> DetailedActivity {
+        let mut fields = self.fields.clone();
+        let defaults = object(json!({
+            "resource_state": 3,
+            "calories": 720.5,
+            "device_name": "Garmin Forerunner 965",
+            "embed_token": "18e4615989b47dd4ff3dc711b0aa4502e4b311a9",
+            "segment_efforts": [],
+            "splits_metric": [],
+            "splits_standard": [],
+            "laps": []
+        }));
+        for (key, value) in defaults {
+            fields.entry(key).or_insert(value);
+        }
+        build(&fields)
+    }
+}
+
+/// Builder of athletes, defaults to the authenticated athlete of Strava's documentation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AthleteBuilder {
+    fields: Map<String, Value>,
+}
+
+impl Default for AthleteBuilder {
+    fn default() -> AthleteBuilder {
+        AthleteBuilder::new()
+    }
+}
+
+impl AthleteBuilder {
+    pub fn new() -> AthleteBuilder {
+        AthleteBuilder {
+            fields: object(json!({
+                "id": 134815,
+                "username": "marianne_t",
+                "resource_state": 3,
+                "firstname": "Marianne",
+                "lastname": "T.",
+                "city": "San Francisco",
+                "state": "CA",
+                "country": "US",
+                "sex": "F",
+                "premium": true,
+                "summit": true,
+                "created_at": "2017-11-14T02:30:05Z",
+                "updated_at": "2018-02-06T19:32:20Z",
+                "badge_type_id": 4,
+                "profile_medium": "https://dgalywyr863hv.cloudfront.net/pictures/athletes/134815/medium.jpg",
+                "profile": "https://dgalywyr863hv.cloudfront.net/pictures/athletes/134815/large.jpg",
+                "follower_count": 5,
+                "friend_count": 5,
+                "mutual_friend_count": 0,
+                "athlete_type": 1,
+                "date_preference": "%m/%d/%Y",
+                "measurement_preference": "feet",
+                "clubs": [],
+                "weight": 61.2,
+                "bikes": [],
+                "shoes": []
+            })),
+        }
+    }
+
+    /// Sets any field by its API name, for fields without a dedicated setter
+    pub fn field(mut self, key: &str, value: impl Serialize) -> AthleteBuilder {
+        set(&mut self.fields, key, value);
+        self
+    }
+
+    pub fn id(self, id: AthleteId) -> AthleteBuilder {
+        self.field("id", id)
+    }
+
+    pub fn name(self, firstname: &str, lastname: &str) -> AthleteBuilder {
+        self.field("firstname", firstname)
+            .field("lastname", lastname)
+    }
+
+    /// Sets the city, state and country, None for an athlete who did not fill them in
+    pub fn location(self, location: Option<(&str, &str, &str)>) -> AthleteBuilder {
+        self.field("city", location.map(|(city, _, _)| city))
+            .field("state", location.map(|(_, state, _)| state))
+            .field("country", location.map(|(_, _, country)| country))
+    }
+
+    pub fn sex(self, sex: Option<Sex>) -> AthleteBuilder {
+        self.field("sex", sex)
+    }
+
+    pub fn weight(self, weight: Option<Weight>) -> AthleteBuilder {
+        self.field("weight", weight)
+    }
+
+    pub fn ftp(self, ftp: Option<u32>) -> AthleteBuilder {
+        self.field("ftp", ftp)
+    }
+
+    pub fn measurement_preference(self, preference: MeasurementPreference) -> AthleteBuilder {
+        self.field("measurement_preference", preference)
+    }
+
+    /// Adds a bike to the athlete, the id must start with `b`
+    pub fn bike(self, id: &str, name: &str, distance: Distance) -> AthleteBuilder {
+        self.gear("bikes", id, name, distance)
+    }
+
+    /// Adds shoes to the athlete, the id must start with `g`
+    pub fn shoes(self, id: &str, name: &str, distance: Distance) -> AthleteBuilder {
+        self.gear("shoes", id, name, distance)
+    }
+
+    fn gear(mut self, key: &str, id: &str, name: &str, distance: Distance) -> AthleteBuilder {
+        let primary = self.fields[key]
+            .as_array()
+            .is_none_or(|gear| gear.is_empty());
+        let gear = json!({
+            "id": id,
+            "primary": primary,
+            "name": name,
+            "resource_state": 2,
+            "distance": distance
+        });
+        match self.fields.get_mut(key) {
+            Some(Value::Array(list)) => list.push(gear),
+            _ => {
+                self.fields.insert(key.to_string(), json!([gear]));
+            }
+        }
+        self
+    }
+
+    /// Returns the athlete as Strava's JSON
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.fields.clone())
+    }
+
+    pub fn build(&self) -> DetailedAthlete {
+        build(&self.fields)
+    }
+
+    pub fn build_summary(&self) -> SummaryAthlete {
+        let mut fields = self.fields.clone();
+        fields.insert("resource_state".to_string(), json!(2));
+        build(&fields)
+    }
+
+    pub fn build_meta(&self) -> MetaAthlete {
+        build(&object(
+            json!({"id": self.fields["id"], "resource_state": 1}),
+        ))
+    }
+}
+
+/// Builder of clubs, defaults to a public cycling club the athlete is a member of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClubBuilder {
+    fields: Map<String, Value>,
+}
+
+impl Default for ClubBuilder {
+    fn default() -> ClubBuilder {
+        ClubBuilder::new()
+    }
+}
+
+impl ClubBuilder {
+    pub fn new() -> ClubBuilder {
+        ClubBuilder {
+            fields: object(json!({
+                "id": 1,
+                "resource_state": 3,
+                "name": "Team Strava Cycling",
+                "profile_medium": "https://dgalywyr863hv.cloudfront.net/pictures/clubs/1/1582/4/medium.jpg",
+                "profile": "https://dgalywyr863hv.cloudfront.net/pictures/clubs/1/1582/4/large.jpg",
+                "cover_photo": "https://dgalywyr863hv.cloudfront.net/pictures/clubs/1/4328276/1/large.jpg",
+                "cover_photo_small": "https://dgalywyr863hv.cloudfront.net/pictures/clubs/1/4328276/1/small.jpg",
+                "activity_types": ["Ride", "VirtualRide", "EBikeRide", "Velomobile", "Handcycle"],
+                "activity_types_icon": "sports_bike_normal",
+                "dimensions": ["distance", "num_activities", "best_activities", "elev_gain", "moving_time", "velocity"],
+                "sport_type": "cycling",
+                "localized_sport_type": "Cycling",
+                "city": "San Francisco",
+                "state": "California",
+                "country": "United States",
+                "private": false,
+                "member_count": 116,
+                "featured": false,
+                "verified": false,
+                "url": "team-strava-bike",
+                "membership": "member",
+                "admin": false,
+                "owner": false,
+                "following_count": 0
+            })),
+        }
+    }
+
+    /// Sets any field by its API name, for fields without a dedicated setter
+    pub fn field(mut self, key: &str, value: impl Serialize) -> ClubBuilder {
+        set(&mut self.fields, key, value);
+        self
+    }
+
+    pub fn id(self, id: ClubId) -> ClubBuilder {
+        self.field("id", id)
+    }
+
+    pub fn name(self, name: &str) -> ClubBuilder {
+        self.field("name", name)
+    }
+
+    pub fn sport_type(self, sport_type: ClubSportType) -> ClubBuilder {
+        self.field("sport_type", sport_type)
+    }
+
+    pub fn member_count(self, member_count: i64) -> ClubBuilder {
+        self.field("member_count", member_count)
+    }
+
+    pub fn private(self, private: bool) -> ClubBuilder {
+        self.field("private", private)
+    }
+
+    /// Sets the membership of the athlete, None when not a member
+    pub fn membership(self, membership: Option<Membership>) -> ClubBuilder {
+        self.field("membership", membership)
+    }
+
+    /// Makes the athlete an admin, or the owner and an admin
+    pub fn admin(self, admin: bool, owner: bool) -> ClubBuilder {
+        self.field("admin", admin || owner).field("owner", owner)
+    }
+
+    /// Returns the club as Strava's JSON
+    pub fn to_json(&self) -> Value {
+        Value::Object(self.fields.clone())
+    }
+
+    pub fn build(&self) -> DetailedClub {
+        build(&self.fields)
+    }
+
+    pub fn build_summary(&self) -> SummaryClub {
+        let mut fields = self.fields.clone();
+        fields.insert("resource_state".to_string(), json!(2));
+        build(&fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_round_trip;
+
+    /// The built model serializes back to exactly the JSON of the builder
+    fn assert_serializes_as<T: Serialize>(model: &T, json: Value) {
+        assert_eq!(serde_json::to_value(model).unwrap(), json);
+    }
+
+    #[test]
+    fn builders_build_models_that_round_trip() {
+        let activity = ActivityBuilder::new()
+            .id(ActivityId(42))
+            .sport_type(SportType::VirtualRow)
+            .distance(Distance(6000.0))
+            .moving_time(Duration(1500.0))
+            .visibility(Visibility::OnlyMe);
+        let summary = activity.build();
+        assert_eq!(summary.map.id.as_deref(), Some("a42"));
+        assert_eq!(summary.average_speed, Speed(4.0));
+        assert_eq!(summary.activity_type.as_ref().unwrap().as_str(), "Workout");
+        assert!(summary.private && summary.is_only_me());
+        assert_eq!(summary.start_date_epoch(), Some(1704898800));
+        assert_round_trip(&summary);
+        assert_round_trip(&activity.build_detailed());
+
+        let athlete = AthleteBuilder::new()
+            .location(None)
+            .weight(None)
+            .bike("b1", "Road bike", Distance(1000.0))
+            .bike("b2", "Gravel bike", Distance(2000.0));
+        let detailed = athlete.build();
+        assert_eq!(detailed.get_city(), None);
+        assert!(detailed.get_bikes()[0].primary && !detailed.get_bikes()[1].primary);
+        assert_round_trip(&detailed);
+        assert_eq!(athlete.build_meta().id, AthleteId(134815));
+        assert_eq!(athlete.build_summary().get_full_name(), "Marianne T.");
+
+        let club = ClubBuilder::new().admin(false, true).build();
+        assert!(club.admin && club.owner);
+        assert_eq!(club.sport_type, Some(ClubSportType::Cycling));
+        assert_round_trip(&club);
+    }
+
+    #[test]
+    fn built_models_serialize_to_the_builder_json() {
+        let activity = ActivityBuilder::new();
+        let athlete = AthleteBuilder::new().location(None).weight(None);
+        let club = ClubBuilder::new();
+
+        assert_serializes_as(&activity.build(), activity.to_json());
+        assert_serializes_as(&athlete.build(), athlete.to_json());
+        assert_serializes_as(&club.build(), club.to_json());
+    }
+}
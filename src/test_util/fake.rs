@@ -0,0 +1,458 @@
+//! # Fake data
+//! Generator of random but plausible athletes, clubs, activities and streams.
+//! Seed the generator to get the same data on every run.
+
+use crate::models::activities::{SportType, SummaryActivity, Visibility};
+use crate::models::athlete::{DetailedAthlete, Sex};
+use crate::models::clubs::{ClubSportType, DetailedClub};
+use crate::models::geo::LatLng;
+use crate::models::ids::{ActivityId, AthleteId, ClubId, GearId};
+use crate::models::streams::{Stream, StreamSet};
+use crate::models::units::{Distance, Duration, Elevation, Speed, Weight};
+use crate::test_util::builders::{ActivityBuilder, AthleteBuilder, ClubBuilder};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Cities activities start from, with their state, country, timezone and UTC offset in seconds
+const CITIES: [(&str, &str, &str, f64, f64, &str, i64); 4] = [
+    (
+        "San Francisco",
+        "CA",
+        "US",
+        37.77,
+        -122.45,
+        "(GMT-08:00) America/Los_Angeles",
+        -28800,
+    ),
+    (
+        "Boulder",
+        "CO",
+        "US",
+        40.01,
+        -105.27,
+        "(GMT-07:00) America/Denver",
+        -25200,
+    ),
+    (
+        "Annecy",
+        "Auvergne-Rhône-Alpes",
+        "FR",
+        45.90,
+        6.12,
+        "(GMT+01:00) Europe/Paris",
+        3600,
+    ),
+    (
+        "Girona",
+        "Catalonia",
+        "ES",
+        41.98,
+        2.82,
+        "(GMT+01:00) Europe/Madrid",
+        3600,
+    ),
+];
+
+const FIRST_NAMES: [&str; 8] = [
+    "Marianne", "Jeff", "Ana", "Kilian", "Emma", "Tadej", "Lena", "Sam",
+];
+const LAST_NAMES: [&str; 8] = [
+    "T.", "Smith", "Garcia", "J.", "Müller", "P.", "Nguyen", "O'Brien",
+];
+
+/// Sport of a fake activity with its range of distance in km, speed in m/s and climbing in m per km
+struct Profile {
+    sport_type: SportType,
+    kilometers: (f64, f64),
+    speed: (f64, f64),
+    climbing: (f64, f64),
+}
+
+const PROFILES: [Profile; 8] = [
+    Profile {
+        sport_type: SportType::Run,
+        kilometers: (3.0, 21.1),
+        speed: (2.5, 4.5),
+        climbing: (2.0, 15.0),
+    },
+    Profile {
+        sport_type: SportType::TrailRun,
+        kilometers: (5.0, 30.0),
+        speed: (1.8, 3.2),
+        climbing: (20.0, 60.0),
+    },
+    Profile {
+        sport_type: SportType::Ride,
+        kilometers: (20.0, 120.0),
+        speed: (6.0, 10.0),
+        climbing: (5.0, 20.0),
+    },
+    Profile {
+        sport_type: SportType::GravelRide,
+        kilometers: (20.0, 90.0),
+        speed: (5.0, 8.0),
+        climbing: (8.0, 25.0),
+    },
+    Profile {
+        sport_type: SportType::VirtualRide,
+        kilometers: (15.0, 60.0),
+        speed: (7.0, 10.0),
+        climbing: (0.0, 15.0),
+    },
+    Profile {
+        sport_type: SportType::Walk,
+        kilometers: (1.0, 8.0),
+        speed: (1.1, 1.6),
+        climbing: (0.0, 10.0),
+    },
+    Profile {
+        sport_type: SportType::Hike,
+        kilometers: (5.0, 20.0),
+        speed: (0.8, 1.4),
+        climbing: (40.0, 100.0),
+    },
+    Profile {
+        sport_type: SportType::Swim,
+        kilometers: (1.0, 4.0),
+        speed: (0.6, 1.0),
+        climbing: (0.0, 0.0),
+    },
+];
+
+/// Seconds between two stream samples
+const SAMPLE_INTERVAL: i64 = 5;
+
+/// Generator of fake models, serialized exactly like Strava's JSON.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::test_util::Faker;
+///
+/// let mut faker = Faker::seeded(42);
+/// let activities = faker.activities(10);
+/// let streams = faker.streams(&activities[0]);
+/// assert_eq!(activities.len(), 10);
+/// assert!(!streams.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Faker {
+    rng: StdRng,
+}
+
+impl Default for Faker {
+    fn default() -> Faker {
+        Faker::new()
+    }
+}
+
+impl Faker {
+    /// Create a generator giving different data on every run
+    pub fn new() -> Faker {
+        Faker {
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Create a generator giving the same data for the same seed
+    pub fn seeded(seed: u64) -> Faker {
+        Faker {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns a random activity started during 2024
+    pub fn activity(&mut self) -> SummaryActivity {
+        let start = self.rng.gen_range(1704067200..1735689600);
+        self.activity_builder(start).build()
+    }
+
+    /// Returns activities of a single athlete, newest first like `get_athlete_activities`
+    pub fn activities(&mut self, count: usize) -> Vec<SummaryActivity> {
+        let athlete_id = AthleteId(self.rng.gen_range(1..100_000_000));
+        let mut start = 1735689600;
+        (0..count)
+            .map(|_| {
+                start -= self.rng.gen_range(43_200..259_200);
+                self.activity_builder(start).athlete_id(athlete_id).build()
+            })
+            .collect()
+    }
+
+    /// Returns a builder of a random activity starting at the epoch seconds
+    pub fn activity_builder(&mut self, start_date: i64) -> ActivityBuilder {
+        let profile = PROFILES
+            .choose(&mut self.rng)
+            .expect("Profiles are not empty");
+        let (_, _, _, lat, lng, timezone, utc_offset) =
+            *CITIES.choose(&mut self.rng).expect("Cities are not empty");
+        let distance = Distance::from_kilometers(
+            self.rng
+                .gen_range(profile.kilometers.0..=profile.kilometers.1),
+        );
+        let speed = self.rng.gen_range(profile.speed.0..=profile.speed.1);
+        let moving_time = Duration((distance.meters() / speed).round());
+        let climbing = self.rng.gen_range(profile.climbing.0..=profile.climbing.1);
+        let ride = profile.sport_type.is_ride();
+
+        let start = self.near(LatLng::new(lat, lng), 0.05);
+        let end = self.near(start, 0.02);
+        let hour = (start_date + utc_offset).rem_euclid(86_400) / 3_600;
+        let name = format!("{} {}", part_of_day(hour), profile.sport_type);
+        let average_heartrate = self.rng.gen_range(120.0..165.0);
+        let heartrate = self
+            .rng
+            .gen_bool(0.8)
+            .then_some((average_heartrate, average_heartrate + 20.0));
+        let watts = (ride && self.rng.gen_bool(0.6)).then(|| self.rng.gen_range(140.0..280.0));
+        let gear_id = match profile.sport_type {
+            SportType::Swim | SportType::VirtualRide => None,
+            _ if ride => Some(GearId(format!("b{}", self.rng.gen_range(1000..99999)))),
+            _ => Some(GearId(format!("g{}", self.rng.gen_range(1000..99999)))),
+        };
+        let visibility = if self.rng.gen_bool(0.1) {
+            Visibility::OnlyMe
+        } else {
+            Visibility::Everyone
+        };
+
+        ActivityBuilder::new()
+            .id(ActivityId(
+                self.rng.gen_range(10_000_000_000..20_000_000_000),
+            ))
+            .sport_type(profile.sport_type.clone())
+            .name(&name)
+            .timezone(timezone, utc_offset)
+            .start_date(start_date)
+            .distance(distance)
+            .moving_time(moving_time)
+            .elapsed_time(Duration(
+                moving_time.seconds() + self.rng.gen_range(0.0..600.0_f64).round(),
+            ))
+            .total_elevation_gain(Elevation((distance.kilometers() * climbing).round()))
+            .max_speed(Speed(speed * self.rng.gen_range(1.3..1.8)))
+            .route(start, end)
+            .gear_id(gear_id)
+            .trainer(profile.sport_type.is_virtual())
+            .commute(ride && self.rng.gen_bool(0.1))
+            .visibility(visibility)
+            .heartrate(heartrate)
+            .watts(watts)
+            .field("kudos_count", self.rng.gen_range(0..40))
+            .field("achievement_count", self.rng.gen_range(0..10))
+    }
+
+    /// Returns a random athlete
+    pub fn athlete(&mut self) -> DetailedAthlete {
+        let (city, state, country, ..) =
+            *CITIES.choose(&mut self.rng).expect("Cities are not empty");
+        let sex = [Sex::Female, Sex::Male].choose(&mut self.rng).cloned();
+        let mut builder = AthleteBuilder::new()
+            .id(AthleteId(self.rng.gen_range(1..100_000_000)))
+            .name(
+                FIRST_NAMES
+                    .choose(&mut self.rng)
+                    .expect("Names are not empty"),
+                LAST_NAMES
+                    .choose(&mut self.rng)
+                    .expect("Names are not empty"),
+            )
+            .location(Some((city, state, country)))
+            .sex(sex)
+            .weight(Some(Weight(self.rng.gen_range(50.0..90.0_f64).round())))
+            .ftp(self.rng.gen_bool(0.5).then(|| self.rng.gen_range(180..320)));
+        for index in 0..self.rng.gen_range(0..3) {
+            let id = format!("b{}", self.rng.gen_range(1000..99999));
+            let distance = Distance(self.rng.gen_range(0.0..20_000_000.0_f64).round());
+            builder = builder.bike(&id, &format!("Bike {}", index + 1), distance);
+        }
+        for index in 0..self.rng.gen_range(0..4) {
+            let id = format!("g{}", self.rng.gen_range(1000..99999));
+            let distance = Distance(self.rng.gen_range(0.0..1_000_000.0_f64).round());
+            builder = builder.shoes(&id, &format!("Shoes {}", index + 1), distance);
+        }
+        builder.build()
+    }
+
+    /// Returns a random club
+    pub fn club(&mut self) -> DetailedClub {
+        let (city, ..) = *CITIES.choose(&mut self.rng).expect("Cities are not empty");
+        let (sport_type, sport) = [
+            (ClubSportType::Cycling, "Cycling"),
+            (ClubSportType::Running, "Running"),
+            (ClubSportType::Triathlon, "Triathlon"),
+        ]
+        .choose(&mut self.rng)
+        .cloned()
+        .expect("Sports are not empty");
+        ClubBuilder::new()
+            .id(ClubId(self.rng.gen_range(1..2_000_000)))
+            .name(&format!("{} {} Club", city, sport))
+            .sport_type(sport_type)
+            .field("localized_sport_type", sport)
+            .field("city", city)
+            .member_count(self.rng.gen_range(2..50_000))
+            .private(self.rng.gen_bool(0.2))
+            .build()
+    }
+
+    /// Returns streams matching the activity, sampled every 5 seconds of its moving time.
+    /// Heart rate is only present when the activity has it, and power only for rides
+    pub fn streams(&mut self, activity: &SummaryActivity) -> StreamSet {
+        let samples = (activity.moving_time.seconds() as i64 / SAMPLE_INTERVAL).max(1) as usize + 1;
+        let average_speed = activity.average_speed.meters_per_second();
+        let mut position = match activity.start_latlng.as_slice() {
+            [lat, lng] => LatLng::new(*lat, *lng),
+            _ => LatLng::new(CITIES[0].3, CITIES[0].4),
+        };
+        let mut bearing: f64 = self.rng.gen_range(0.0..std::f64::consts::TAU);
+        let mut altitude = activity.elev_low.map_or(10.0, |elev| elev.meters());
+
+        let mut time = Vec::with_capacity(samples);
+        let mut distance = Vec::with_capacity(samples);
+        let mut latlng = Vec::with_capacity(samples);
+        let mut altitudes = Vec::with_capacity(samples);
+        let mut velocity = Vec::with_capacity(samples);
+        let mut grade = Vec::with_capacity(samples);
+        let mut covered = 0.0;
+        for index in 0..samples {
+            let speed = if index == 0 {
+                0.0
+            } else {
+                (average_speed * self.rng.gen_range(0.8..1.2)).max(0.0)
+            };
+            let step = speed * SAMPLE_INTERVAL as f64;
+            let slope: f64 = if step > 0.0 {
+                self.rng.gen_range(-0.06..0.06)
+            } else {
+                0.0
+            };
+            bearing += self.rng.gen_range(-0.3..0.3);
+            position = LatLng::new(
+                position.lat + step * bearing.cos() / 111_320.0,
+                position.lng + step * bearing.sin() / (111_320.0 * position.lat.to_radians().cos()),
+            );
+            covered += step;
+            altitude = (altitude + step * slope).max(0.0);
+
+            time.push(index as i64 * SAMPLE_INTERVAL);
+            distance.push(round(covered, 1));
+            latlng.push([round(position.lat, 6), round(position.lng, 6)]);
+            altitudes.push(round(altitude, 1));
+            velocity.push(round(speed, 3));
+            grade.push(round(slope * 100.0, 1));
+        }
+
+        let heartrate = activity.average_heartrate.map(|average| {
+            (0..samples)
+                .map(|_| (average + self.rng.gen_range(-12.0..12.0)).round() as i64)
+                .collect()
+        });
+        let cadence_average = if activity.sport_type.is_ride() {
+            88
+        } else {
+            84
+        };
+        let cadence = (0..samples)
+            .map(|_| cadence_average + self.rng.gen_range(-6..6))
+            .collect();
+        let watts = activity.average_watts.map(|average| {
+            (0..samples)
+                .map(|_| {
+                    self.rng
+                        .gen_bool(0.9)
+                        .then(|| (average * self.rng.gen_range(0.5..1.5)).round() as i64)
+                })
+                .collect()
+        });
+
+        StreamSet {
+            time: Some(stream(time)),
+            distance: Some(stream(distance)),
+            latlng: Some(stream(latlng)),
+            altitude: Some(stream(altitudes)),
+            velocity_smooth: Some(stream(velocity)),
+            heartrate: heartrate.map(stream),
+            cadence: Some(stream(cadence)),
+            watts: watts.map(stream),
+            temp: None,
+            moving: Some(stream(vec![true; samples])),
+            grade_smooth: Some(stream(grade)),
+        }
+    }
+
+    /// Returns a point within about `degrees` of the center
+    fn near(&mut self, center: LatLng, degrees: f64) -> LatLng {
+        LatLng::new(
+            round(center.lat + self.rng.gen_range(-degrees..degrees), 6),
+            round(center.lng + self.rng.gen_range(-degrees..degrees), 6),
+        )
+    }
+}
+
+/// Wraps samples in a stream as returned at high resolution
+fn stream<T>(data: Vec<T>) -> Stream<T> {
+    Stream {
+        original_size: Some(data.len() as i64),
+        data,
+        series_type: Some("distance".to_string()),
+        resolution: Some("high".to_string()),
+    }
+}
+
+/// Rounds to the number of decimals, as the API does
+fn round(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+/// Returns the part of the day Strava names activities after
+fn part_of_day(hour: i64) -> &'static str {
+    match hour {
+        5..=11 => "Morning",
+        12..=16 => "Afternoon",
+        17..=20 => "Evening",
+        _ => "Night",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::assert_round_trip;
+
+    #[test]
+    fn seeded_faker_generates_the_same_data() {
+        let activities = Faker::seeded(7).activities(5);
+        let again = Faker::seeded(7).activities(5);
+        assert_eq!(
+            serde_json::to_value(&activities).unwrap(),
+            serde_json::to_value(&again).unwrap()
+        );
+        assert!(activities
+            .windows(2)
+            .all(|pair| pair[0].start_date_epoch() > pair[1].start_date_epoch()));
+        assert!(activities
+            .iter()
+            .all(|activity| activity.athlete.id == activities[0].athlete.id));
+    }
+
+    #[test]
+    fn fake_models_round_trip_through_json() {
+        let mut faker = Faker::seeded(2024);
+        for _ in 0..20 {
+            let activity = faker.activity();
+            assert_round_trip(&activity);
+
+            let streams = faker.streams(&activity);
+            assert_eq!(
+                streams.has(crate::models::streams::StreamType::Watts),
+                activity.average_watts.is_some()
+            );
+            assert_round_trip(&streams);
+        }
+        assert_round_trip(&faker.athlete());
+        assert_round_trip(&faker.club());
+    }
+}
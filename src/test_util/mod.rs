@@ -0,0 +1,19 @@
+//! # Test utilities
+//! Builders and a fake data generator of the models, for testing code that uses this crate.
+//! Models serialize exactly like Strava's JSON, leaving out the optional fields that are `None`,
+//! so their output can be served from a mock server.
+//! Requires the `test-util` feature.
+
+pub mod builders;
+pub mod fake;
+
+pub use builders::{ActivityBuilder, AthleteBuilder, ClubBuilder};
+pub use fake::Faker;
+
+/// Serializing a model and deserializing it again gives the same JSON
+#[cfg(test)]
+pub(crate) fn assert_round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(model: &T) {
+    let json = serde_json::to_value(model).unwrap();
+    let again: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&again).unwrap(), json);
+}
@@ -1,5 +1,9 @@
 //! # Module: util
 //! Contains submodules to handle the redirect server and the config file that stores the access token and refresh token.
+//! Also contains the webhook event server and the polyline and timestamp conversions shared by the models.
 
 pub mod auth_config;
+pub mod polyline;
 pub mod redirect;
+pub mod timestamp;
+pub mod webhook;
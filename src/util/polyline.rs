@@ -0,0 +1,219 @@
+//! # polyline.rs
+//! Decodes and encodes the Google encoded polylines used by Strava for maps and segments,
+//! with helpers to measure and simplify the decoded coordinates.
+//! Format: https://developers.google.com/maps/documentation/utilities/polylinealgorithm
+
+use crate::models::geo::{BoundingBox, LatLng, EARTH_RADIUS};
+use crate::models::units::Distance;
+use std::error::Error;
+
+/// Precision of the coordinates, Strava encodes them with 5 decimal places
+const PRECISION: f64 = 1e5;
+
+/// Reads one zigzag encoded value starting at the iterator position
+fn decode_value(chars: &mut impl Iterator<Item = u8>) -> Result<i64, Box<dyn Error>> {
+    let mut result: i64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = chars
+            .next()
+            .ok_or("Polyline ended in the middle of a value")?;
+        if !(63..127).contains(&byte) {
+            return Err(format!("Invalid polyline character {:?}", byte as char).into());
+        }
+        if shift > 60 {
+            return Err("Polyline value is too long".into());
+        }
+        let chunk = (byte - 63) as i64;
+        result |= (chunk & 0x1f) << shift;
+        shift += 5;
+        if chunk < 0x20 {
+            break;
+        }
+    }
+    Ok(if result & 1 == 1 {
+        !(result >> 1)
+    } else {
+        result >> 1
+    })
+}
+
+/// Decode an encoded polyline into its coordinates.
+///
+/// # Arguments
+///
+/// * `polyline` - The encoded polyline, e.g. `Map.summary_polyline`.
+///
+/// # Returns
+///
+/// * `Result<Vec<LatLng>>` - The coordinates in order, an empty polyline decodes to no points.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::util::polyline;
+///
+/// let points = polyline::decode("_p~iF~ps|U_ulLnnqC_mqNvxq`@").unwrap();
+/// assert_eq!(points.len(), 3);
+/// assert_eq!(points[0].lat, 38.5);
+/// ```
+pub fn decode(polyline: &str) -> Result<Vec<LatLng>, Box<dyn Error>> {
+    let mut chars = polyline.bytes().peekable();
+    let mut points = Vec::new();
+    let (mut lat, mut lng) = (0i64, 0i64);
+
+    while chars.peek().is_some() {
+        lat += decode_value(&mut chars)?;
+        lng += decode_value(&mut chars)?;
+        points.push(LatLng::new(lat as f64 / PRECISION, lng as f64 / PRECISION));
+    }
+    Ok(points)
+}
+
+/// Appends one zigzag encoded value to the output
+fn encode_value(value: i64, output: &mut String) {
+    let mut value = if value < 0 { !(value << 1) } else { value << 1 };
+    while value >= 0x20 {
+        output.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+        value >>= 5;
+    }
+    output.push((value as u8 + 63) as char);
+}
+
+/// Encode coordinates into a polyline, rounding them to 5 decimal places.
+///
+/// # Examples
+///
+/// ```
+/// use strava_client_rs::models::geo::LatLng;
+/// use strava_client_rs::util::polyline;
+///
+/// let points = [LatLng::new(38.5, -120.2), LatLng::new(40.7, -120.95)];
+/// assert_eq!(polyline::encode(&points), "_p~iF~ps|U_ulLnnqC");
+/// ```
+pub fn encode(points: &[LatLng]) -> String {
+    let mut output = String::new();
+    let (mut previous_lat, mut previous_lng) = (0i64, 0i64);
+    for point in points {
+        let lat = (point.lat * PRECISION).round() as i64;
+        let lng = (point.lng * PRECISION).round() as i64;
+        encode_value(lat - previous_lat, &mut output);
+        encode_value(lng - previous_lng, &mut output);
+        (previous_lat, previous_lng) = (lat, lng);
+    }
+    output
+}
+
+/// Returns the smallest box containing all the points, or None if there are no points
+pub fn bounding_box(points: &[LatLng]) -> Option<BoundingBox> {
+    BoundingBox::around(points)
+}
+
+/// Returns the length of the line going through the points in order
+pub fn length(points: &[LatLng]) -> Distance {
+    Distance(
+        points
+            .windows(2)
+            .map(|pair| pair[0].distance_to(pair[1]))
+            .sum(),
+    )
+}
+
+/// Distance in meters from a point to the segment between start and end,
+/// on a local equirectangular projection which is accurate at the scale of a track
+fn distance_to_segment(point: LatLng, start: LatLng, end: LatLng) -> f64 {
+    let scale = start.lat.to_radians().cos();
+    let project = |p: LatLng| {
+        (
+            (p.lng - start.lng).to_radians() * scale * EARTH_RADIUS,
+            (p.lat - start.lat).to_radians() * EARTH_RADIUS,
+        )
+    };
+    let (x, y) = project(point);
+    let (end_x, end_y) = project(end);
+    let squared_length = end_x * end_x + end_y * end_y;
+    let t = if squared_length == 0.0 {
+        0.0
+    } else {
+        ((x * end_x + y * end_y) / squared_length).clamp(0.0, 1.0)
+    };
+    (x - t * end_x).hypot(y - t * end_y)
+}
+
+/// Simplify a line with the Douglas–Peucker algorithm.
+///
+/// # Arguments
+///
+/// * `points` - The coordinates of the line.
+/// * `tolerance` - The maximum distance in meters between the line and the simplified line.
+///
+/// # Returns
+///
+/// * `Vec<LatLng>` - The kept points in order, always including the first and last points.
+pub fn simplify(points: &[LatLng], tolerance: f64) -> Vec<LatLng> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = ranges.pop() {
+        let farthest = (first + 1..last)
+            .map(|index| {
+                let distance = distance_to_segment(points[index], points[first], points[last]);
+                (index, distance)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, distance)) = farthest {
+            if distance > tolerance {
+                keep[index] = true;
+                ranges.push((first, index));
+                ranges.push((index, last));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(point, kept)| kept.then_some(*point))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_round_trips_decode() {
+        let encoded = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+        let points = decode(encoded).unwrap();
+        assert_eq!(encode(&points), encoded);
+        assert_eq!(encode(&[]), "");
+        assert!(decode("_p~iF~ps|U_").is_err());
+    }
+
+    #[test]
+    fn measure_and_simplify_line() {
+        let points = [
+            LatLng::new(0.0, 0.0),
+            LatLng::new(0.0, 0.005),
+            LatLng::new(0.00001, 0.01),
+            LatLng::new(0.01, 0.01),
+        ];
+
+        let bounds = bounding_box(&points).unwrap();
+        assert_eq!(bounds.south_west, LatLng::new(0.0, 0.0));
+        assert_eq!(bounds.north_east, LatLng::new(0.01, 0.01));
+        assert!(bounding_box(&[]).is_none());
+
+        // two sides of a 0.01 degree square at the equator, about 1112 m each
+        assert!((length(&points).meters() - 2224.0).abs() < 2.0);
+
+        let simplified = simplify(&points, 5.0);
+        assert_eq!(simplified, vec![points[0], points[2], points[3]]);
+        assert_eq!(simplify(&points, 5000.0), vec![points[0], points[3]]);
+    }
+}
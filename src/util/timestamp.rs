@@ -0,0 +1,117 @@
+//! # timestamp.rs
+//! Converts between epoch seconds and the `2025-03-26T23:27:40Z` timestamps used by the Strava API.
+//! Civil date algorithms from http://howardhinnant.github.io/date_algorithms.html
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Days since the epoch of a civil date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Civil date as (year, month, day) of a number of days since the epoch
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Parses a timestamp in the `2025-03-26T23:27:40Z` format returned by the API into epoch seconds.
+/// Returns None if the timestamp is not in that format.
+pub fn parse_utc_timestamp(timestamp: &str) -> Option<i64> {
+    let timestamp = timestamp.strip_suffix('Z')?;
+    let (date, time) = timestamp.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second)
+}
+
+/// Converts a time to epoch seconds, times before the epoch are clamped to 0
+pub fn system_time_to_epoch(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Formats epoch seconds as a timestamp in the `2025-03-26T23:27:40Z` format used by the API
+pub fn format_utc_timestamp(epoch_seconds: i64) -> String {
+    format!("{}Z", format_local_timestamp(epoch_seconds))
+}
+
+/// Formats seconds since `1970-01-01T00:00:00` local time as a naive timestamp in the
+/// `2025-03-27T08:27:40` format, without the `Z` that would mark it as UTC
+pub fn format_local_timestamp(local_seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(local_seconds.div_euclid(SECONDS_PER_DAY));
+    let seconds = local_seconds.rem_euclid(SECONDS_PER_DAY);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_utc_timestamp_to_epoch() {
+        assert_eq!(parse_utc_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_utc_timestamp("2024-02-29T12:30:15Z"),
+            Some(1709209815)
+        );
+        assert_eq!(parse_utc_timestamp("2024-02-29"), None);
+    }
+
+    #[test]
+    fn format_utc_timestamp_round_trips() {
+        assert_eq!(format_utc_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_timestamp(1709209815), "2024-02-29T12:30:15Z");
+        for epoch in [-86401, 951782400, 1735689599, 4102444800] {
+            assert_eq!(
+                parse_utc_timestamp(&format_utc_timestamp(epoch)),
+                Some(epoch)
+            );
+        }
+    }
+
+    #[test]
+    fn system_time_to_epoch_clamps_times_before_the_epoch() {
+        let time = UNIX_EPOCH + std::time::Duration::from_secs(1709209815);
+        assert_eq!(system_time_to_epoch(time), 1709209815);
+        assert_eq!(
+            system_time_to_epoch(UNIX_EPOCH - std::time::Duration::from_secs(1)),
+            0
+        );
+    }
+}
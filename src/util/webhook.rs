@@ -0,0 +1,750 @@
+//! # webhook.rs
+//! Set up a server receiving the events Strava pushes to the callback of the push subscription.
+//! It answers the subscription validation by echoing `hub.challenge` when `hub.verify_token`
+//! matches, and parses the POSTed events into `WebhookEvent` before passing them to a handler.
+//! Strava expects every event to be acknowledged within two seconds, so the handler must not do
+//! slow work itself: `queue::EventQueue` stores the events on disk before they are acknowledged,
+//! and a worker processes them afterwards with retries.
+
+use crate::models::webhooks::WebhookEvent;
+use log::{info, trace, warn};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, Response, Server};
+use url::Url;
+
+/// ## mod server
+/// uses tiny_http crate to bind to the address the reverse proxy forwards the callback to
+/// Calls the handler with every event received, until the server is dropped
+pub mod server {
+    use super::*;
+
+    /// Response to send back to Strava, and the event to dispatch if the request held one
+    #[derive(Debug, PartialEq)]
+    pub(crate) struct Reply {
+        pub status: u16,
+        pub body: String,
+        pub event: Option<WebhookEvent>,
+    }
+
+    impl Reply {
+        fn new(status: u16, body: &str) -> Reply {
+            Reply {
+                status,
+                body: body.to_string(),
+                event: None,
+            }
+        }
+    }
+
+    /// Decides how to answer a request to the callback
+    ///
+    /// GET requests are subscription validations, POST requests carry an event.
+    pub(crate) fn handle(method: &Method, url: &str, body: &str, verify_token: &str) -> Reply {
+        match method {
+            Method::Get => {
+                // Tiny-Http doesn't parse the URL, so we have to do it ourselves.
+                let url = match Url::parse(&format!("http://localhost{}", url)) {
+                    Ok(url) => url,
+                    Err(_) => return Reply::new(400, "Invalid URL"),
+                };
+                let query = |name: &str| {
+                    url.query_pairs()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.into_owned())
+                };
+
+                if query("hub.mode").as_deref() != Some("subscribe") {
+                    return Reply::new(400, "Unsupported hub.mode");
+                }
+                if query("hub.verify_token").as_deref() != Some(verify_token) {
+                    warn!("Subscription validation with an invalid verify token");
+                    return Reply::new(403, "Invalid verify token");
+                }
+                let challenge = query("hub.challenge").unwrap_or_default();
+                trace!("Subscription validation challenge: {}", challenge);
+                Reply::new(
+                    200,
+                    &serde_json::json!({ "hub.challenge": challenge }).to_string(),
+                )
+            }
+            Method::Post => match serde_json::from_str::<WebhookEvent>(body) {
+                Ok(event) => Reply {
+                    status: 200,
+                    body: String::new(),
+                    event: Some(event),
+                },
+                Err(e) => {
+                    warn!("Could not parse webhook event: {}", e);
+                    Reply::new(400, "Invalid event")
+                }
+            },
+            _ => Reply::new(405, "Method not allowed"),
+        }
+    }
+
+    /// Server receiving the webhook events of the push subscription
+    pub struct WebhookServer {
+        server: Server,
+        verify_token: String,
+    }
+
+    impl WebhookServer {
+        /// Bind the server to the address, e.g. `0.0.0.0:8080`
+        ///
+        /// `verify_token` must match the one given when creating the push subscription.
+        pub fn bind(address: &str, verify_token: &str) -> Result<WebhookServer, Box<dyn Error>> {
+            let server = Server::http(address).map_err(|e| -> Box<dyn Error> {
+                warn!("Could not bind to {}: {}", address, e);
+                e
+            })?;
+            info!("Listening on {} for Strava webhook events.", address);
+            Ok(WebhookServer {
+                server,
+                verify_token: verify_token.to_string(),
+            })
+        }
+
+        /// Returns the address the server is bound to
+        pub fn local_addr(&self) -> Option<SocketAddr> {
+            self.server.server_addr().to_ip()
+        }
+
+        /// Answer the next request, calling the handler if it holds an event.
+        ///
+        /// The event is acknowledged only when the handler succeeds, otherwise Strava is
+        /// answered with an error and retries the event later.
+        pub fn handle_next<F>(&self, handler: &mut F) -> Result<(), Box<dyn Error>>
+        where
+            F: FnMut(WebhookEvent) -> Result<(), Box<dyn Error>>,
+        {
+            let mut req = self.server.recv()?;
+            let mut body = String::new();
+            req.as_reader().read_to_string(&mut body)?;
+            trace!("Webhook request: {} {}", req.method(), req.url());
+
+            let mut reply = handle(req.method(), req.url(), &body, &self.verify_token);
+            if let Some(event) = reply.event.take() {
+                if let Err(e) = handler(event) {
+                    warn!("Webhook handler failed: {}", e);
+                    reply = Reply::new(500, "Handler failed");
+                }
+            }
+
+            let content_type = Header::from_bytes("Content-Type", "application/json")
+                .expect("Content-Type header is valid");
+            let response = Response::from_string(reply.body)
+                .with_status_code(reply.status)
+                .with_header(content_type);
+            req.respond(response)?;
+            Ok(())
+        }
+
+        /// Answer requests forever, calling the handler with every event received.
+        /// Failures to answer a request are logged and the server keeps listening.
+        pub fn serve<F>(&self, mut handler: F)
+        where
+            F: FnMut(WebhookEvent) -> Result<(), Box<dyn Error>>,
+        {
+            loop {
+                if let Err(e) = self.handle_next(&mut handler) {
+                    warn!("Could not answer webhook request: {}", e);
+                }
+            }
+        }
+
+        /// Answer requests forever, appending every event to the queue before acknowledging it.
+        /// Run `EventQueue::run_worker` on another thread to process the events.
+        pub fn serve_queue(&self, queue: &super::queue::EventQueue) {
+            self.serve(|event| queue.push(event).map(|_| ()))
+        }
+    }
+}
+
+/// ## mod queue
+/// Durable queue of webhook events stored in a directory
+/// * `events.jsonl` every event received, one JSON line each, kept until `compact` so they can be replayed
+/// * `cursor` the sequence number of the last event the worker finished with, and the byte offset
+///   of the end of its line so the worker only reads the new events
+/// * `dead_letter.jsonl` events that still failed after the last retry
+///
+/// A queue directory must only be used by one process at a time.
+pub mod queue {
+    use super::*;
+
+    const EVENTS_FILE: &str = "events.jsonl";
+    const CURSOR_FILE: &str = "cursor";
+    const DEAD_LETTER_FILE: &str = "dead_letter.jsonl";
+    const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+    const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    /// An event stored in the queue, numbered in the order it was received
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct QueuedEvent {
+        pub seq: u64,
+        pub received_at: i64, // epoch seconds
+        pub event: WebhookEvent,
+    }
+
+    /// An event the handler failed to process after every attempt
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct DeadLetter {
+        pub queued: QueuedEvent,
+        pub attempts: u32,
+        pub error: String,
+        pub failed_at: i64, // epoch seconds
+    }
+
+    /// Returns the current time as epoch seconds
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0)
+    }
+
+    /// Reads the complete JSON lines of a file from a byte offset, with the offset of the end of
+    /// each line. A missing file has no lines, and the last line is skipped while it is still
+    /// being written. Malformed lines are logged and skipped so they don't block the queue.
+    /// An offset not at the start of a line, e.g. after the file was compacted, reads from the start.
+    fn read_lines_from<T: serde::de::DeserializeOwned>(
+        path: &Path,
+        offset: u64,
+    ) -> Result<Vec<(T, u64)>, Box<dyn Error>> {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut content = Vec::new();
+        let mut start = 0;
+        if offset > 0 {
+            file.seek(SeekFrom::Start(offset - 1))?;
+            file.read_to_end(&mut content)?;
+            if content.first() == Some(&b'\n') {
+                start = offset;
+            } else {
+                content.clear();
+                file.seek(SeekFrom::Start(0))?;
+            }
+        }
+        if start == 0 {
+            file.read_to_end(&mut content)?;
+        }
+        let data = if start > 0 {
+            &content[1..]
+        } else {
+            &content[..]
+        };
+
+        let mut lines = Vec::new();
+        let mut end = start;
+        for line in data.split_inclusive(|byte| *byte == b'\n') {
+            if line.last() != Some(&b'\n') {
+                break;
+            }
+            end += line.len() as u64;
+            let line = String::from_utf8_lossy(line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(value) => lines.push((value, end)),
+                Err(e) => warn!(
+                    "Skipping malformed line of {:?} ending at byte {}: {}",
+                    path, end, e
+                ),
+            }
+        }
+        Ok(lines)
+    }
+
+    /// Reads every complete JSON line of a file, see `read_lines_from`
+    fn read_lines<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Vec<T>, Box<dyn Error>> {
+        Ok(read_lines_from(path, 0)?
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect())
+    }
+
+    /// Truncates a line left without its newline by a write interrupted by a crash,
+    /// so the next line is not appended onto it
+    fn truncate_partial_line(path: &Path) -> Result<(), Box<dyn Error>> {
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let complete = content
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |end| end + 1);
+        if complete < content.len() {
+            warn!(
+                "Truncating {} bytes of an interrupted write at the end of {:?}",
+                content.len() - complete,
+                path
+            );
+            let file = OpenOptions::new().write(true).open(path)?;
+            file.set_len(complete as u64)?;
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+
+    /// Appends a JSON line to a file and flushes it to disk
+    fn append_line<T: Serialize>(path: &Path, value: &T) -> Result<(), Box<dyn Error>> {
+        let mut line = serde_json::to_string(value)?;
+        line.push('\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    /// Durable queue of webhook events
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// use strava_client_rs::util::webhook::queue::EventQueue;
+    /// use strava_client_rs::util::webhook::server::WebhookServer;
+    ///
+    /// let queue = Arc::new(EventQueue::open("webhook_events").unwrap().max_attempts(3));
+    /// let worker_queue = Arc::clone(&queue);
+    /// thread::spawn(move || {
+    ///     worker_queue.run_worker(Duration::from_secs(1), |event| {
+    ///         println!("Processing {:?} {}", event.object_type, event.object_id);
+    ///         Ok(())
+    ///     })
+    /// });
+    ///
+    /// let server = WebhookServer::bind("0.0.0.0:8080", "STRAVA").unwrap();
+    /// server.serve_queue(&queue);
+    /// ```
+    #[derive(Debug)]
+    pub struct EventQueue {
+        dir: PathBuf,
+        next_seq: Mutex<u64>,
+        processing: Mutex<()>,
+        max_attempts: u32,
+        retry_delay: Duration,
+    }
+
+    /// Position of the worker in the event log
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    struct Cursor {
+        seq: u64,
+        offset: u64,
+    }
+
+    impl EventQueue {
+        /// Open the queue stored in the directory, creating the directory if needed.
+        /// A line left incomplete by a crash while it was written is truncated.
+        pub fn open<P: AsRef<Path>>(dir: P) -> Result<EventQueue, Box<dyn Error>> {
+            let dir = dir.as_ref().to_path_buf();
+            fs::create_dir_all(&dir)?;
+            truncate_partial_line(&dir.join(EVENTS_FILE))?;
+            truncate_partial_line(&dir.join(DEAD_LETTER_FILE))?;
+            let events: Vec<QueuedEvent> = read_lines(&dir.join(EVENTS_FILE))?;
+            let mut queue = EventQueue {
+                dir,
+                next_seq: Mutex::new(1),
+                processing: Mutex::new(()),
+                max_attempts: DEFAULT_MAX_ATTEMPTS,
+                retry_delay: DEFAULT_RETRY_DELAY,
+            };
+            // The cursor keeps the numbering going once compaction removed every event
+            let last_seq = events
+                .last()
+                .map_or(0, |queued| queued.seq)
+                .max(queue.cursor()?.seq);
+            queue.next_seq = Mutex::new(last_seq + 1);
+            trace!(
+                "Opened webhook queue {:?} at seq {}",
+                queue.dir,
+                last_seq + 1
+            );
+            Ok(queue)
+        }
+
+        /// Number of times the handler is called for an event before it is dead-lettered
+        pub fn max_attempts(mut self, max_attempts: u32) -> EventQueue {
+            self.max_attempts = max_attempts.max(1);
+            self
+        }
+
+        /// Time to wait before retrying an event, doubled after every failed attempt
+        pub fn retry_delay(mut self, retry_delay: Duration) -> EventQueue {
+            self.retry_delay = retry_delay;
+            self
+        }
+
+        /// Append an event to the queue, it is on disk when this returns
+        ///
+        /// Returns the sequence number of the event.
+        pub fn push(&self, event: WebhookEvent) -> Result<u64, Box<dyn Error>> {
+            let mut next_seq = self.next_seq.lock().map_err(|e| e.to_string())?;
+            let queued = QueuedEvent {
+                seq: *next_seq,
+                received_at: now(),
+                event,
+            };
+            append_line(&self.dir.join(EVENTS_FILE), &queued)?;
+            *next_seq += 1;
+            trace!("Queued webhook event {}", queued.seq);
+            Ok(queued.seq)
+        }
+
+        /// Returns the position of the last event the worker finished with, seq 0 if none.
+        /// A cursor without an offset reads the event log from the start
+        fn cursor(&self) -> Result<Cursor, Box<dyn Error>> {
+            let content = match fs::read_to_string(self.dir.join(CURSOR_FILE)) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Cursor::default()),
+                Err(e) => return Err(e.into()),
+            };
+            let mut parts = content.split_whitespace();
+            let seq = parts.next().ok_or("Empty webhook queue cursor")?.parse()?;
+            let offset = parts.next().map(str::parse).transpose()?.unwrap_or(0);
+            Ok(Cursor { seq, offset })
+        }
+
+        /// Atomically moves the cursor past the event
+        fn set_cursor(&self, cursor: Cursor) -> Result<(), Box<dyn Error>> {
+            let tmp = self.dir.join(format!("{}.tmp", CURSOR_FILE));
+            let mut file = File::create(&tmp)?;
+            file.write_all(format!("{} {}", cursor.seq, cursor.offset).as_bytes())?;
+            file.sync_data()?;
+            fs::rename(tmp, self.dir.join(CURSOR_FILE))?;
+            Ok(())
+        }
+
+        /// Returns the events after the cursor, with the offset of the end of their line
+        fn pending_from(&self, cursor: Cursor) -> Result<Vec<(QueuedEvent, u64)>, Box<dyn Error>> {
+            Ok(
+                read_lines_from::<QueuedEvent>(&self.dir.join(EVENTS_FILE), cursor.offset)?
+                    .into_iter()
+                    .filter(|(queued, _)| queued.seq > cursor.seq)
+                    .collect(),
+            )
+        }
+
+        /// Returns every event received, in order
+        pub fn events(&self) -> Result<Vec<QueuedEvent>, Box<dyn Error>> {
+            read_lines(&self.dir.join(EVENTS_FILE))
+        }
+
+        /// Returns the events the worker has not finished with yet
+        pub fn pending(&self) -> Result<Vec<QueuedEvent>, Box<dyn Error>> {
+            Ok(self
+                .pending_from(self.cursor()?)?
+                .into_iter()
+                .map(|(queued, _)| queued)
+                .collect())
+        }
+
+        /// Returns the events that still failed after the last attempt
+        pub fn dead_letters(&self) -> Result<Vec<DeadLetter>, Box<dyn Error>> {
+            read_lines(&self.dir.join(DEAD_LETTER_FILE))
+        }
+
+        /// Process every pending event in order, retrying failures and dead-lettering the
+        /// events still failing after the last attempt.
+        ///
+        /// Returns the number of events processed successfully.
+        pub fn process_pending<F>(&self, handler: &mut F) -> Result<usize, Box<dyn Error>>
+        where
+            F: FnMut(&WebhookEvent) -> Result<(), Box<dyn Error>>,
+        {
+            let _processing = self.processing.lock().map_err(|e| e.to_string())?;
+            let mut processed = 0;
+            for (queued, offset) in self.pending_from(self.cursor()?)? {
+                let mut delay = self.retry_delay;
+                let mut attempt = 1;
+                loop {
+                    match handler(&queued.event) {
+                        Ok(()) => {
+                            processed += 1;
+                            break;
+                        }
+                        Err(e) if attempt >= self.max_attempts => {
+                            warn!(
+                                "Dead-lettering webhook event {} after {} attempts: {}",
+                                queued.seq, attempt, e
+                            );
+                            let dead_letter = DeadLetter {
+                                queued: queued.clone(),
+                                attempts: attempt,
+                                error: e.to_string(),
+                                failed_at: now(),
+                            };
+                            append_line(&self.dir.join(DEAD_LETTER_FILE), &dead_letter)?;
+                            break;
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Webhook event {} failed attempt {}: {}",
+                                queued.seq, attempt, e
+                            );
+                            thread::sleep(delay);
+                            delay *= 2;
+                            attempt += 1;
+                        }
+                    }
+                }
+                self.set_cursor(Cursor {
+                    seq: queued.seq,
+                    offset,
+                })?;
+            }
+            Ok(processed)
+        }
+
+        /// Remove the events the worker has finished with from the event log, so it stops
+        /// growing. They can't be replayed afterwards, dead letters are kept.
+        ///
+        /// Returns the number of events removed.
+        pub fn compact(&self) -> Result<usize, Box<dyn Error>> {
+            let _processing = self.processing.lock().map_err(|e| e.to_string())?;
+            let _next_seq = self.next_seq.lock().map_err(|e| e.to_string())?;
+            let cursor = self.cursor()?;
+            let path = self.dir.join(EVENTS_FILE);
+            let (done, pending): (Vec<QueuedEvent>, Vec<QueuedEvent>) = read_lines(&path)?
+                .into_iter()
+                .partition(|queued: &QueuedEvent| queued.seq <= cursor.seq);
+            if done.is_empty() {
+                return Ok(0);
+            }
+
+            let tmp = self.dir.join(format!("{}.tmp", EVENTS_FILE));
+            let mut file = File::create(&tmp)?;
+            for queued in &pending {
+                let mut line = serde_json::to_string(queued)?;
+                line.push('\n');
+                file.write_all(line.as_bytes())?;
+            }
+            file.sync_data()?;
+            // Reading from the start is always right, so the offset is reset before the log shrinks
+            self.set_cursor(Cursor {
+                seq: cursor.seq,
+                offset: 0,
+            })?;
+            fs::rename(tmp, path)?;
+            info!("Compacted {} processed webhook events", done.len());
+            Ok(done.len())
+        }
+
+        /// Process pending events forever, checking for new events every poll interval.
+        /// Failures to read or write the queue are logged and retried at the next poll.
+        /// The event log is not compacted, call `compact` once the events are no longer needed.
+        pub fn run_worker<F>(&self, poll_interval: Duration, mut handler: F)
+        where
+            F: FnMut(&WebhookEvent) -> Result<(), Box<dyn Error>>,
+        {
+            info!("Processing webhook events from {:?}", self.dir);
+            loop {
+                if let Err(e) = self.process_pending(&mut handler) {
+                    warn!("Could not process webhook queue: {}", e);
+                }
+                thread::sleep(poll_interval);
+            }
+        }
+
+        /// Returns the events whose Strava event time is within the range, bounds included
+        pub fn events_between(
+            &self,
+            from: i64,
+            to: i64,
+        ) -> Result<Vec<QueuedEvent>, Box<dyn Error>> {
+            Ok(self
+                .events()?
+                .into_iter()
+                .filter(|queued| (from..=to).contains(&queued.event.event_time))
+                .collect())
+        }
+
+        /// Call the handler again with the events whose Strava event time is within the range.
+        /// Replaying does not move the cursor, and stops at the first error.
+        ///
+        /// Returns the number of events replayed.
+        pub fn replay<F>(&self, from: i64, to: i64, mut handler: F) -> Result<usize, Box<dyn Error>>
+        where
+            F: FnMut(&WebhookEvent) -> Result<(), Box<dyn Error>>,
+        {
+            let events = self.events_between(from, to)?;
+            info!("Replaying {} webhook events", events.len());
+            for queued in &events {
+                handler(&queued.event)?;
+            }
+            Ok(events.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::server::*;
+    use super::*;
+    use crate::models::webhooks::AspectType;
+    use std::thread;
+
+    #[test]
+    fn validation_echoes_challenge_only_with_matching_token() {
+        let url = "/webhook?hub.mode=subscribe&hub.challenge=15f7d1a91c1f40f8a748fd134752feb3&hub.verify_token=STRAVA";
+
+        let reply = handle(&Method::Get, url, "", "STRAVA");
+        assert_eq!(reply.status, 200);
+        assert_eq!(
+            reply.body,
+            r#"{"hub.challenge":"15f7d1a91c1f40f8a748fd134752feb3"}"#
+        );
+        assert_eq!(handle(&Method::Get, url, "", "OTHER").status, 403);
+    }
+
+    #[test]
+    fn server_dispatches_posted_event_to_handler() {
+        let server = WebhookServer::bind("127.0.0.1:0", "STRAVA").unwrap();
+        let url = format!("http://{}/webhook", server.local_addr().unwrap());
+        let client = thread::spawn(move || {
+            reqwest::blocking::Client::new()
+                .post(url)
+                .body(r#"{"aspect_type":"create","event_time":1516126040,"object_id":1360128428,"object_type":"activity","owner_id":134815,"subscription_id":120475,"updates":{}}"#)
+                .send()
+                .unwrap()
+                .status()
+        });
+
+        let mut events = Vec::new();
+        server
+            .handle_next(&mut |event| {
+                events.push(event);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(client.join().unwrap(), 200);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].aspect_type, AspectType::Create);
+        assert_eq!(events[0].object_id, 1360128428);
+    }
+
+    /// Returns a new directory for a queue
+    fn queue_dir() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "strava-webhook-queue-{}-{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ))
+    }
+
+    fn event(object_id: i64, event_time: i64) -> WebhookEvent {
+        serde_json::from_str(&format!(
+            r#"{{"aspect_type":"create","event_time":{event_time},"object_id":{object_id},"object_type":"activity","owner_id":134815,"subscription_id":120475}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn queue_retries_dead_letters_and_replays() {
+        let dir = queue_dir();
+        let queue = queue::EventQueue::open(&dir)
+            .unwrap()
+            .max_attempts(2)
+            .retry_delay(Duration::ZERO);
+        queue.push(event(1, 1000)).unwrap();
+        queue.push(event(2, 2000)).unwrap();
+        queue.push(event(3, 3000)).unwrap();
+
+        let mut calls = Vec::new();
+        let processed = queue
+            .process_pending(&mut |event| {
+                calls.push(event.object_id);
+                if event.object_id == 2 {
+                    return Err("activity not found".into());
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(processed, 2);
+        assert_eq!(calls, vec![1, 2, 2, 3]);
+        assert!(queue.pending().unwrap().is_empty());
+        let dead_letters = queue.dead_letters().unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].queued.event.object_id, 2);
+        assert_eq!(dead_letters[0].attempts, 2);
+
+        // Reopening keeps the numbering and the cursor
+        let queue = queue::EventQueue::open(&dir).unwrap();
+        assert_eq!(queue.push(event(4, 4000)).unwrap(), 4);
+        assert_eq!(queue.pending().unwrap().len(), 1);
+
+        let mut replayed = Vec::new();
+        queue
+            .replay(2000, 3000, |event| {
+                replayed.push(event.object_id);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(replayed, vec![2, 3]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn queue_recovers_from_torn_writes_and_compacts() {
+        let dir = queue_dir();
+        let queue = queue::EventQueue::open(&dir).unwrap();
+        queue.push(event(1, 1000)).unwrap();
+        queue.push(event(2, 2000)).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(dir.join("events.jsonl"))
+            .unwrap();
+        file.write_all(br#"{"seq":3,"received_at":"#).unwrap();
+
+        // Reopening after the crash drops the torn line instead of appending onto it
+        let queue = queue::EventQueue::open(&dir).unwrap();
+        assert_eq!(queue.push(event(3, 3000)).unwrap(), 3);
+        let mut calls = Vec::new();
+        let mut handler = |event: &WebhookEvent| -> Result<(), Box<dyn Error>> {
+            calls.push(event.object_id);
+            Ok(())
+        };
+        assert_eq!(queue.process_pending(&mut handler).unwrap(), 3);
+
+        // A malformed line is skipped, the events after it are still processed
+        file.write_all(b"not json\n").unwrap();
+        queue.push(event(4, 4000)).unwrap();
+        assert_eq!(queue.process_pending(&mut handler).unwrap(), 1);
+        assert_eq!(calls, vec![1, 2, 3, 4]);
+
+        queue.push(event(5, 5000)).unwrap();
+        assert_eq!(queue.compact().unwrap(), 4);
+        assert_eq!(queue.events().unwrap().len(), 1);
+        assert_eq!(queue.pending().unwrap()[0].event.object_id, 5);
+
+        // Numbering goes on after the processed events were removed
+        queue.process_pending(&mut |_| Ok(())).unwrap();
+        assert_eq!(queue.compact().unwrap(), 1);
+        let queue = queue::EventQueue::open(&dir).unwrap();
+        assert_eq!(queue.push(event(6, 6000)).unwrap(), 6);
+        assert_eq!(queue.pending().unwrap().len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}